@@ -0,0 +1,34 @@
+import test from "ava";
+
+import { BatchStatement, BatchType, Consistency, SerialConsistency } from "../index.js";
+
+test("BatchStatement is logged by default", (t) => {
+  t.is(new BatchStatement().getType(), BatchType.Logged);
+});
+
+test("Create a BatchStatement of each BatchType", (t) => {
+  t.is(new BatchStatement(BatchType.Logged).getType(), BatchType.Logged);
+  t.is(new BatchStatement(BatchType.Unlogged).getType(), BatchType.Unlogged);
+  t.is(new BatchStatement(BatchType.Counter).getType(), BatchType.Counter);
+});
+
+test("Counter BatchStatement options are unset by default", (t) => {
+  const batch = new BatchStatement(BatchType.Counter);
+  t.is(batch.getConsistency(), null);
+  t.is(batch.getSerialConsistency(), null);
+  t.is(batch.getTimestamp(), null);
+  t.false(batch.getIsIdempotent());
+  t.false(batch.getTracing());
+});
+
+test("Counter BatchStatement keeps its type when options are set", (t) => {
+  const batch = new BatchStatement(BatchType.Counter);
+  batch.setConsistency(Consistency.Quorum);
+  batch.setSerialConsistency(SerialConsistency.LocalSerial);
+  batch.setTracing(true);
+
+  t.is(batch.getType(), BatchType.Counter);
+  t.is(batch.getConsistency(), Consistency.Quorum);
+  t.is(batch.getSerialConsistency(), SerialConsistency.LocalSerial);
+  t.true(batch.getTracing());
+});
@@ -0,0 +1,30 @@
+import test from "ava";
+
+import { Cluster } from "../index.js";
+
+// Needs a running cluster, e.g. `CLUSTER_NODES=127.0.0.1:9042`
+const nodes = process.env.CLUSTER_NODES?.split(",");
+const clusterTest = nodes ? test : test.skip;
+
+clusterTest("executeConcurrent does not send the next chunk after a failure with stopOnError", async (t) => {
+  const cluster = new Cluster({ nodes: nodes ?? [] });
+  const session = await cluster.connect();
+
+  await session.execute(
+    "CREATE KEYSPACE IF NOT EXISTS concurrent_spec WITH REPLICATION = { 'class' : 'SimpleStrategy', 'replication_factor' : 1 }",
+  );
+  await session.execute("CREATE TABLE IF NOT EXISTS concurrent_spec.numbers (id int PRIMARY KEY)");
+  await session.execute("TRUNCATE concurrent_spec.numbers");
+  const insert = await session.prepare("INSERT INTO concurrent_spec.numbers (id) VALUES (?)");
+
+  // Chunks hold `concurrency` rows: the second row of the first chunk cannot be bound as an int
+  const result = await session.executeConcurrent(insert, [[0], ["not an int"], [2], [3]], {
+    concurrency: 2,
+    stopOnError: true,
+  });
+
+  t.is(result.failed, 1);
+  t.is(result.errors[0]?.index, 1);
+  t.is(result.unsent, 2);
+  t.deepEqual(await session.execute("SELECT id FROM concurrent_spec.numbers WHERE id IN (2, 3)"), []);
+});
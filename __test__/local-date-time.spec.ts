@@ -0,0 +1,45 @@
+import test from "ava";
+
+import { LocalDate, LocalTime } from "../index.js";
+
+test("Create a LocalDate from its components", (t) => {
+  const date = new LocalDate(2024, 2, 29);
+  t.is(date.toString(), "2024-02-29");
+  t.is(date.year, 2024);
+  t.is(date.month, 2);
+  t.is(date.day, 29);
+});
+
+test("Create a LocalDate from a string", (t) => {
+  const date = LocalDate.fromString("1970-01-02");
+  t.is(date.daysSinceEpoch, 1);
+});
+
+test("Create a LocalDate before the epoch", (t) => {
+  const date = LocalDate.fromDaysSinceEpoch(-1);
+  t.is(date.toString(), "1969-12-31");
+});
+
+test("Should error on creating an invalid LocalDate", (t) => {
+  t.throws(() => new LocalDate(2023, 2, 29), { instanceOf: Error });
+  t.throws(() => LocalDate.fromString("2023-13-01"), { instanceOf: Error });
+});
+
+test("Create a LocalTime from its components", (t) => {
+  const time = new LocalTime(13, 5, 7, 123_000_000);
+  t.is(time.toString(), "13:05:07.123");
+  t.is(time.nanosecondOfDay, ((13 * 60 + 5) * 60 + 7) * 1e9 + 123_000_000);
+});
+
+test("Create a LocalTime from a string", (t) => {
+  const time = LocalTime.fromString("23:59:59.999999999");
+  t.is(time.hour, 23);
+  t.is(time.minute, 59);
+  t.is(time.second, 59);
+  t.is(time.nanosecond, 999_999_999);
+});
+
+test("Should error on creating an out of range LocalTime", (t) => {
+  t.throws(() => new LocalTime(24, 0, 0), { instanceOf: Error });
+  t.throws(() => LocalTime.fromNanosecondOfDay(86_400_000_000_000), { instanceOf: Error });
+});
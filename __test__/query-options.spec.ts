@@ -0,0 +1,52 @@
+import test from "ava";
+
+import { Consistency, CustomRetryPolicy, ExecutionProfileHandle, Query, RetryDecision, RetryPolicy } from "../index.js";
+
+test("Query options are unset by default", (t) => {
+  const query = new Query("SELECT * FROM users");
+  t.is(query.getConsistency(), null);
+  t.is(query.getRequestTimeout(), null);
+  t.is(query.getTimestamp(), null);
+  t.is(query.getRetryPolicy(), null);
+  t.false(query.getIsIdempotent());
+  t.false(query.getTracing());
+});
+
+test("Query options can be set and read back", (t) => {
+  const query = new Query("SELECT * FROM users");
+  query.setConsistency(Consistency.Quorum);
+  query.setRequestTimeout(2000);
+  query.setTimestamp(1_700_000_000_000_000);
+  query.setRetryPolicy(RetryPolicy.Fallthrough);
+  query.setIsIdempotent(true);
+  query.setTracing(true);
+
+  t.is(query.getConsistency(), Consistency.Quorum);
+  t.is(query.getRequestTimeout(), 2000);
+  t.is(query.getTimestamp(), 1_700_000_000_000_000);
+  t.is(query.getRetryPolicy(), RetryPolicy.Fallthrough);
+  t.true(query.getIsIdempotent());
+  t.true(query.getTracing());
+
+  query.setRequestTimeout(null);
+  t.is(query.getRequestTimeout(), null);
+});
+
+test("Query accepts a custom retry policy", (t) => {
+  const query = new Query("SELECT * FROM users");
+  query.setRetryPolicy(new CustomRetryPolicy(() => RetryDecision.RetryNextNode));
+  t.true(query.getRetryPolicy() instanceof CustomRetryPolicy);
+
+  t.throws(() => query.setRetryPolicy({} as CustomRetryPolicy), { instanceOf: Error });
+});
+
+test("Query shares the execution profile it is given", (t) => {
+  const profile = new ExecutionProfileHandle({ consistency: Consistency.LocalQuorum });
+  const query = new Query("SELECT * FROM users");
+  query.setExecutionProfile(profile);
+
+  t.deepEqual(query.getExecutionProfile()?.profile, { consistency: Consistency.LocalQuorum });
+
+  query.setExecutionProfile(null);
+  t.is(query.getExecutionProfile(), null);
+});
@@ -0,0 +1,44 @@
+import test from "ava";
+
+import { Consistency, CustomRetryPolicy, RetryDecision, RetryInfo } from "../index.js";
+
+const info: RetryInfo = {
+  errorName: "ReadTimeoutError",
+  message: "Read timeout",
+  attempt: 1,
+  isIdempotent: true,
+  consistency: Consistency.Quorum,
+};
+
+test("CustomRetryPolicy decides from its callback", async (t) => {
+  const policy = new CustomRetryPolicy(({ errorName, attempt, isIdempotent }) =>
+    errorName === "ReadTimeoutError" && isIdempotent && attempt < 3 ? RetryDecision.RetryNextNode : RetryDecision.Rethrow,
+  );
+
+  t.is(await policy.decide(info), RetryDecision.RetryNextNode);
+  t.is(await policy.decide({ ...info, attempt: 3 }), RetryDecision.Rethrow);
+  t.is(await policy.decide({ ...info, isIdempotent: false }), RetryDecision.Rethrow);
+});
+
+test("CustomRetryPolicy rethrows when the callback fails", async (t) => {
+  const throwing = new CustomRetryPolicy(() => {
+    throw new Error("Failed to decide");
+  });
+  const invalid = new CustomRetryPolicy(() => "retry" as unknown as RetryDecision);
+
+  t.is(await throwing.decide(info), RetryDecision.Rethrow);
+  t.is(await invalid.decide(info), RetryDecision.Rethrow);
+});
+
+test("CustomRetryPolicy gives up when the callback is too slow", async (t) => {
+  const policy = new CustomRetryPolicy(
+    () => {
+      const end = Date.now() + 200;
+      while (Date.now() < end);
+      return RetryDecision.RetrySameNode;
+    },
+    { decisionTimeout: 20 },
+  );
+
+  t.is(await policy.decide(info), null);
+});
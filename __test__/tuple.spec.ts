@@ -0,0 +1,23 @@
+import test from "ava";
+
+import { Tuple } from "../index.js";
+
+test("Create a Tuple from its elements", (t) => {
+  const tuple = new Tuple([1, "a", null]);
+  t.is(tuple.toString(), '[Some(Int(1)), Some(Text("a")), None]');
+});
+
+test("Create nested and empty Tuples", (t) => {
+  t.is(new Tuple([]).toString(), "[]");
+  t.is(new Tuple([new Tuple([1n])]).toString(), "[Some(Tuple([Some(BigInt(1))]))]");
+});
+
+test("Tuple is frozen at construction", (t) => {
+  const values: Array<number | string | null> = [1, "a"];
+  const tuple = new Tuple(values);
+
+  values[0] = 2;
+  values.push(null);
+
+  t.is(tuple.toString(), '[Some(Int(1)), Some(Text("a"))]');
+});
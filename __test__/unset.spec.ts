@@ -0,0 +1,13 @@
+import test from "ava";
+
+import { UNSET, Unset } from "../index.js";
+
+test("UNSET is a frozen Unset instance", (t) => {
+  t.true(UNSET instanceof Unset);
+  t.true(Object.isFrozen(UNSET));
+});
+
+test("UNSET is displayed by name", (t) => {
+  t.is(UNSET.toString(), "UNSET");
+  t.is(new Unset().toString(), "UNSET");
+});
@@ -1,4 +1,4 @@
-import { Cluster, BatchStatement, Query, Uuid } from "../index.js";
+import { Cluster, BatchStatement, BatchType, Query, Uuid } from "../index.js";
 
 const nodes = process.env.CLUSTER_NODES?.split(",") ?? ["127.0.0.1:9042"];
 
@@ -12,6 +12,7 @@ await session.execute(
 );
 await session.useKeyspace("batch_statements");
 await session.execute("CREATE TABLE IF NOT EXISTS users (id UUID PRIMARY KEY, name TEXT)");
+await session.execute("CREATE TABLE IF NOT EXISTS page_views (page TEXT PRIMARY KEY, views COUNTER)");
 
 const simpleStatement = new Query("INSERT INTO users (id, name) VALUES (?, ?)");
 const preparedStatement = await session.prepare("INSERT INTO users (id, name) VALUES (?, ?)");
@@ -25,3 +26,16 @@ await session.batch(batch, [
 ]);
 
 console.log(await session.execute("SELECT * FROM users"));
+
+const counterBatch = new BatchStatement(BatchType.Counter);
+const increment = await session.prepare("UPDATE page_views SET views = views + ? WHERE page = ?");
+
+counterBatch.appendStatement(increment);
+counterBatch.appendStatement(increment);
+
+await session.batch(counterBatch, [
+  [1n, "/home"],
+  [3n, "/about"],
+]);
+
+console.log(await session.execute("SELECT * FROM page_views"));
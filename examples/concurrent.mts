@@ -0,0 +1,33 @@
+import { Cluster, Uuid } from "../index.js";
+
+const nodes = process.env.CLUSTER_NODES?.split(",") ?? ["127.0.0.1:9042"];
+
+const cluster = new Cluster({ nodes });
+const session = await cluster.connect();
+
+await session.execute(
+  "CREATE KEYSPACE IF NOT EXISTS concurrent WITH REPLICATION = { 'class' : 'SimpleStrategy', 'replication_factor' : 1 }",
+);
+await session.useKeyspace("concurrent");
+await session.execute("CREATE TABLE IF NOT EXISTS users (id UUID PRIMARY KEY, name TEXT)");
+
+const insert = await session.prepare("INSERT INTO users (id, name) VALUES (?, ?)");
+// Rows are read lazily, so they could as well come from a file or another database
+function* users() {
+  for (let i = 0; i < 10_000; i++) yield [Uuid.randomV4(), `User ${i}`];
+}
+
+const { succeeded, failed, errors } = await session.executeConcurrent(insert, users(), { concurrency: 256 });
+
+console.log(`Inserted ${succeeded} rows, ${failed} failed`);
+for (const { index, name, message } of errors) {
+  console.error(`Row ${index} failed with ${name}: ${message}`);
+}
+
+await session.execute("CREATE TABLE IF NOT EXISTS events (device INT, at TIMESTAMP, value DOUBLE, PRIMARY KEY (device, at))");
+
+const insertEvent = await session.prepare("INSERT INTO events (device, at, value) VALUES (?, ?, ?)");
+const events = Array.from({ length: 10_000 }, (_, i) => [i % 10, new Date(Date.now() + i), Math.random()]);
+
+const { batches, failed: failedEvents } = await session.batchByPartition(insertEvent, events, { maxBatchSize: 50 });
+console.log(`Inserted ${events.length - failedEvents} events in ${batches} batches`);
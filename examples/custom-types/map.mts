@@ -12,16 +12,21 @@ await session.execute(
 );
 await session.useKeyspace("maps");
 
-await session.execute("CREATE TABLE IF NOT EXISTS maps (a uuid, b map<text, int>, primary key (a))");
+await session.execute("CREATE TABLE IF NOT EXISTS maps (a uuid, b map<text, int>, c map<int, text>, primary key (a))");
 
-await session.execute("INSERT INTO maps (a, b) VALUES (?, ?)", [
+await session.execute("INSERT INTO maps (a, b, c) VALUES (?, ?, ?)", [
   Uuid.randomV4(),
   new Map<string, number>([
     ["a", 1],
     ["b", 2],
     ["c", 3],
   ]),
+  new Map<number, string>([
+    [1, "a"],
+    [2, "b"],
+  ]),
 ]);
 
+// Maps with text keys are returned as plain objects, other maps as a JS `Map` keeping the type of the keys
 const results = await session.execute("SELECT * FROM maps");
 console.log(results);
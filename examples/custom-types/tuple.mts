@@ -0,0 +1,22 @@
+import { Cluster, Tuple } from "../../index.js";
+
+const nodes = process.env.CLUSTER_NODES?.split(",") ?? ["127.0.0.1:9042"];
+
+console.log(`Connecting to ${nodes}`);
+
+const cluster = new Cluster({ nodes });
+const session = await cluster.connect();
+
+await session.execute(
+  "CREATE KEYSPACE IF NOT EXISTS tuples WITH REPLICATION = { 'class' : 'SimpleStrategy', 'replication_factor' : 1 }",
+);
+await session.useKeyspace("tuples");
+
+await session.execute("CREATE TABLE IF NOT EXISTS tuples (a text, b tuple<int, text, bigint>, primary key (a))");
+
+await session.execute("INSERT INTO tuples (a, b) VALUES (?, ?)", ["first", new Tuple([1, "one", 1n])]);
+// Elements of a tuple can be null
+await session.execute("INSERT INTO tuples (a, b) VALUES (?, ?)", ["second", new Tuple([2, null, 2n])]);
+
+const results = await session.execute("SELECT * FROM tuples");
+console.log(results);
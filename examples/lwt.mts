@@ -15,6 +15,10 @@ const query = new Query("INSERT INTO lwt.tab (a) VALUES(?) IF NOT EXISTS");
 query.setConsistency(Consistency.One);
 query.setSerialConsistency(SerialConsistency.Serial);
 
-await session.execute(query, [12345]);
+const { applied } = await session.executeConditional(query, [12345]);
+console.log(`Inserted: ${applied}`);
+
+const { applied: appliedAgain, existing } = await session.executeConditional(query, [12345]);
+console.log(`Inserted again: ${appliedAgain}, existing row: ${JSON.stringify(existing)}`);
 
 console.log("Ok.");
@@ -0,0 +1,42 @@
+import { Cluster, Query } from "../index.js";
+
+const nodes = process.env.CLUSTER_NODES?.split(",") ?? ["127.0.0.1:9042"];
+
+console.log(`Connecting to ${nodes}`);
+
+const cluster = new Cluster({ nodes });
+const session = await cluster.connect();
+
+await session.execute(
+  "CREATE KEYSPACE IF NOT EXISTS paging WITH REPLICATION = { 'class' : 'SimpleStrategy', 'replication_factor' : 1 }",
+);
+await session.useKeyspace("paging");
+
+await session.execute("CREATE TABLE IF NOT EXISTS paging (a int, b int, c text, primary key (a, b))");
+
+const prepared = await session.prepare("INSERT INTO paging (a, b, c) VALUES (?, ?, 'abc')");
+for (let i = 0; i < 100; i++) {
+  await session.execute(prepared, [i, 2 * i]);
+}
+
+const query = new Query("SELECT a, b, c FROM paging");
+query.setPageSize(7);
+
+// Pages of 7 rows are fetched as the iterator is consumed
+let count = 0;
+for await (const row of await session.executeIter(query)) {
+  console.log(row);
+  count++;
+}
+console.log(`Iterated over ${count} rows`);
+
+// Pages can also be requested one by one, resuming from a previously returned paging state
+let pagingState: Buffer | undefined = undefined;
+do {
+  const page = await session.executePaged("SELECT a, b, c FROM paging", [], { pageSize: 30, pagingState });
+  console.log(`Fetched a page of ${page.rows.length} rows`);
+  pagingState = page.pagingState;
+} while (pagingState);
+
+const metrics = session.metrics();
+console.log(`Iter queries requested: ${metrics.getQueriesIterNum()}`);
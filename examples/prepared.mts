@@ -21,6 +21,14 @@ await session.execute(prepared, [44, "I'm prepared 3!"]);
 
 await session.execute("INSERT INTO basic (a, b, c) VALUES (?, 7, ?)", [45, "I'm also prepared"], { prepare: true });
 
+const named = await session.prepare("INSERT INTO basic (a, b, c) VALUES (:a, 7, :c)");
+await session.execute(named, { c: "I'm bound by name!", a: 46 });
+
+for (const { name, type } of named.getVariables()) {
+  console.log(`Bind marker ${name}: ${type}`);
+}
+console.log(`Partition key bound by the markers at ${named.getPartitionKeyIndexes()}`);
+
 const metrics = session.metrics();
 console.log(`Queries requested: ${metrics.getQueriesNum()}`);
 console.log(`Iter queries requested: ${metrics.getQueriesIterNum()}`);
@@ -28,3 +36,5 @@ console.log(`Errors occurred: ${metrics.getErrorsNum()}`);
 console.log(`Iter errors occurred: ${metrics.getErrorsIterNum()}`);
 console.log(`Average latency: ${metrics.getLatencyAvgMs()}`);
 console.log(`99.9 latency percentile: ${metrics.getLatencyPercentileMs(99.9)}`);
+console.log(`Prepared statement cache hits: ${metrics.getPreparedCacheHits()}`);
+console.log(`Prepared statement cache misses: ${metrics.getPreparedCacheMisses()}`);
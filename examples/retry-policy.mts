@@ -0,0 +1,29 @@
+import { Cluster, CustomRetryPolicy, Query, RetryDecision, RetryPolicy } from "../index.js";
+
+const nodes = process.env.CLUSTER_NODES?.split(",") ?? ["127.0.0.1:9042"];
+
+const cluster = new Cluster({
+  nodes,
+  defaultExecutionProfile: { retryPolicy: RetryPolicy.Fallthrough },
+});
+const session = await cluster.connect();
+
+await session.execute(
+  "CREATE KEYSPACE IF NOT EXISTS retries WITH REPLICATION = { 'class' : 'SimpleStrategy', 'replication_factor' : 1 }",
+);
+await session.execute("CREATE TABLE IF NOT EXISTS retries.tab (a int PRIMARY KEY)");
+
+const retryTimeouts = new CustomRetryPolicy(({ errorName, attempt, isIdempotent }) => {
+  console.log(`Attempt ${attempt} failed with ${errorName}`);
+
+  if (!isIdempotent || attempt > 3) return RetryDecision.Rethrow;
+  return errorName === "ReadTimeoutError" ? RetryDecision.RetrySameNode : RetryDecision.RetryNextNode;
+});
+
+const query = new Query("SELECT a FROM retries.tab");
+query.setIsIdempotent(true);
+query.setRetryPolicy(retryTimeouts);
+
+console.log(await session.execute(query));
+
+console.log("Ok.");
@@ -8,7 +8,9 @@
  * Only INSERT, UPDATE and DELETE statements are allowed.
  */
 export declare class BatchStatement {
-  constructor()
+  /** Creates an empty batch, logged unless another `batchType` is given */
+  constructor(batchType?: BatchType | undefined | null)
+  getType(): BatchType
   /**
    * Appends a statement to the batch.
    *
@@ -16,6 +18,26 @@ export declare class BatchStatement {
    * Using simple statements with bind markers in batches is strongly discouraged. For each simple statement with a non-empty list of values in the batch, the driver will send a prepare request, and it will be done sequentially. Results of preparation are not cached between `session.batch` calls. Consider preparing the statements before putting them into the batch.
    */
   appendStatement(statement: Query | PreparedStatement): void
+  setConsistency(consistency: Consistency): void
+  /** `null` when the consistency of the execution profile is used */
+  getConsistency(): Consistency | null
+  /** Serial consistency of the conditional statements of the batch */
+  setSerialConsistency(serialConsistency: SerialConsistency): void
+  getSerialConsistency(): SerialConsistency | null
+  /**
+   * Sets the default timestamp of the statements of the batch, in microseconds since the unix epoch.\
+   * `null` lets the database assign the timestamp.
+   */
+  setTimestamp(timestamp?: number | undefined | null): void
+  getTimestamp(): number | null
+  /**
+   * Marks the batch as idempotent, which allows it to be retried or speculatively executed.\
+   * Counter updates are not idempotent, counter batches must not be marked as such.
+   */
+  setIsIdempotent(isIdempotent: boolean): void
+  getIsIdempotent(): boolean
+  setTracing(tracing: boolean): void
+  getTracing(): boolean
 }
 export type ScyllaBatchStatement = BatchStatement
 
@@ -25,6 +47,9 @@ export declare class Cluster {
    * {
    *     nodes: Array<string>,
    * }
+   *
+   * Every node is used as a contact point, so the session can be established as long as one of them is reachable.
+   * Nodes can be given as `ip:port`, `hostname:port` or just `hostname`, in which case the default port 9042 is used.
    */
   constructor(clusterConfig: ClusterConfig)
   /** Connect to the cluster */
@@ -32,6 +57,39 @@ export declare class Cluster {
 }
 export type ScyllaCluster = Cluster
 
+/**
+ * Retry policy deciding from a JS callback what to do after each failed attempt.
+ *
+ * When the callback throws or returns something else than a `RetryDecision`, the error is returned without
+ * retrying.
+ *
+ * # Blocking
+ * The driver decides about retries synchronously, so the callback runs on the main thread while one of the
+ * driver's threads waits for its answer, up to `decisionTimeout`. The other requests keep progressing in the
+ * meantime, but every failure waiting for a decision holds a thread: the callback must be synchronous and
+ * quick, and the main thread must not be busy for long while requests fail. Prefer the policies of
+ * `RetryPolicy` when they fit.
+ *
+ * # Example
+ * ```javascript
+ * const policy = new CustomRetryPolicy(({ errorName, attempt, isIdempotent }) =>
+ *   errorName === "ReadTimeoutError" && isIdempotent && attempt < 3
+ *     ? RetryDecision.RetryNextNode
+ *     : RetryDecision.Rethrow,
+ * );
+ * query.setRetryPolicy(policy);
+ * ```
+ */
+export declare class CustomRetryPolicy {
+  constructor(callback: (info: RetryInfo) => RetryDecision, options?: CustomRetryPolicyOptions)
+  /**
+   * Asks the callback what to do with a failed attempt the way the driver does, from another thread and
+   * with the same timeout.\
+   * Resolves to `null` when the callback did not answer in time.
+   */
+  decide(info: RetryInfo): Promise<RetryDecision | null>
+}
+
 export declare class Decimal {
   constructor(intVal: Array<number>, scale: number)
   /** Returns the string representation of the Decimal. */
@@ -58,6 +116,26 @@ export declare class Duration {
   toString(): string
 }
 
+/**
+ * Execution profile built once, to be shared by statements and requests.
+ *
+ * Building a profile creates its load balancing policy, and with `latencyAwareness` a task measuring the
+ * latency of the nodes. Reusing a handle keeps those measurements, which would start from scratch with
+ * every new profile.
+ *
+ * # Example
+ * ```javascript
+ * const profile = new ExecutionProfileHandle({ consistency: Consistency.LocalQuorum });
+ *
+ * await session.execute(query, params, { executionProfile: profile });
+ * ```
+ */
+export declare class ExecutionProfileHandle {
+  constructor(profile: ExecutionProfile)
+  /** Settings the handle was built from */
+  get profile(): ExecutionProfile
+}
+
 /**
  * A float number.
  *
@@ -69,12 +147,76 @@ export declare class Float {
   toString(): string
 }
 
+/**
+ * Records what happened while executing the statements it is attached to: every attempt, the node it was
+ * sent to, its result and the retry decision taken after a failure.
+ *
+ * # Example
+ * ```javascript
+ * const listener = new HistoryListener();
+ * query.setHistoryListener(listener);
+ *
+ * await session.execute(query);
+ * console.log(listener.getHistory());
+ * ```
+ */
+export declare class HistoryListener {
+  constructor()
+  /** Returns the history collected so far, formatted as readable text */
+  getHistory(): string
+  /** Discards the history collected so far */
+  clear(): void
+}
+
 /** A list of any CqlType */
 export declare class List<T = NativeTypes> {
   constructor(values: T[])
   toString(): string
 }
 
+/**
+ * Native CQL `date` representation, a date without a time zone.
+ *
+ * Unlike `Date`, it represents every date a `date` column can hold, counted in days since the unix epoch.
+ */
+export declare class LocalDate {
+  /** Creates a date from its year, month (1-12) and day (1-31). */
+  constructor(year: number, month: number, day: number)
+  /** Parses a date in the `yyyy-mm-dd` format. */
+  static fromString(str: string): LocalDate
+  /** Creates a date from the number of days since the unix epoch, negative for dates before it. */
+  static fromDaysSinceEpoch(days: number): LocalDate
+  get daysSinceEpoch(): number
+  /** `null` when the date is too far from the epoch to be represented in the gregorian calendar. */
+  get year(): number | null
+  get month(): number | null
+  get day(): number | null
+  /**
+   * Returns the date in the `yyyy-mm-dd` format, or the number of days since the epoch when it is out of the
+   * range of the gregorian calendar.
+   */
+  toString(): string
+}
+
+/** Native CQL `time` representation, a time of day with nanosecond precision and without a time zone. */
+export declare class LocalTime {
+  /** Creates a time from its hour (0-23), minute, second and nanosecond. */
+  constructor(hour: number, minute: number, second: number, nanosecond?: number | undefined | null)
+  /** Parses a time in the `hh:mm:ss[.fffffffff]` format. */
+  static fromString(str: string): LocalTime
+  /** Creates a time from the number of nanoseconds since midnight. */
+  static fromNanosecondOfDay(nanoseconds: number): LocalTime
+  /** Nanoseconds since midnight. */
+  get nanosecondOfDay(): number
+  get hour(): number
+  get minute(): number
+  get second(): number
+  /** Nanoseconds within the current second. */
+  get nanosecond(): number
+  /** Returns the time in the `hh:mm:ss[.fffffffff]` format. */
+  toString(): string
+}
+
 /** A map of any CqlType to any CqlType */
 export declare class Map<T = NativeTypes, U = NativeTypes> {
   constructor(values: Array<Array<T | U>>)
@@ -100,18 +242,110 @@ export declare class Metrics {
    * * `percentile` - float value (0.0 - 100.0), value will be clamped to this range
    */
   getLatencyPercentileMs(percentile: number): bigint
+  /** Returns counter for statements found in the prepared statement cache */
+  getPreparedCacheHits(): bigint
+  /** Returns counter for statements that had to be prepared because they were not in the cache */
+  getPreparedCacheMisses(): bigint
+  /** Returns the number of statements currently in the prepared statement cache */
+  getPreparedCacheSize(): number
 }
 
 export declare class PreparedStatement {
   setConsistency(consistency: Consistency): void
+  /** `null` when the consistency of the execution profile is used */
+  getConsistency(): Consistency | null
   setSerialConsistency(serialConsistency: SerialConsistency): void
+  getSerialConsistency(): SerialConsistency | null
+  setPageSize(pageSize: number): void
+  getPageSize(): number | null
+  /** Sets the request timeout in milliseconds, `null` to use the `requestTimeout` of the execution profile, which is in seconds */
+  setRequestTimeout(timeout?: number | undefined | null): void
+  /** Request timeout in milliseconds, `null` when the `requestTimeout` of the execution profile (in seconds) is used */
+  getRequestTimeout(): number | null
+  /** Marks the statement as idempotent, which allows it to be retried or speculatively executed */
+  setIsIdempotent(isIdempotent: boolean): void
+  getIsIdempotent(): boolean
+  setTracing(tracing: boolean): void
+  getTracing(): boolean
+  /**
+   * Sets the default timestamp of the statement, in microseconds since the unix epoch.\
+   * `null` lets the database assign the timestamp.
+   */
+  setTimestamp(timestamp?: number | undefined | null): void
+  getTimestamp(): number | null
+  /** Sets the retry policy of the statement, `null` to use the one of the execution profile */
+  setRetryPolicy(retryPolicy?: RetryPolicySetting | undefined | null): void
+  getRetryPolicy(): RetryPolicySetting | null
+  /** Sets the execution profile of the statement, `null` to use the session's default one */
+  setExecutionProfile(executionProfile?: ExecutionProfileHandle | undefined | null): void
+  getExecutionProfile(): ExecutionProfileHandle | null
+  /** Records the history of every execution of the statement in `listener` */
+  setHistoryListener(listener: HistoryListener): void
+  removeHistoryListener(): void
+  /** Keyspace of the table the statement operates on, `null` when the statement has no bind markers */
+  getKeyspace(): string | null
+  /** Table the statement operates on, `null` when the statement has no bind markers */
+  getTable(): string | null
+  /** Text of the statement, as it was prepared */
+  getStatement(): string
+  /** Id assigned to the statement by the database */
+  getId(): Buffer
+  /** Bind markers of the statement, in the order their values are expected */
+  getVariables(): Array<ColumnSpecification>
+  /** Columns of the rows returned by the statement, empty for statements that do not return rows */
+  getResultColumns(): Array<ColumnSpecification>
+  /**
+   * Indexes of the bind markers that make up the partition key, in the order of the partition key columns.\
+   * Empty when the partition key is not fully bound, in which case the statement is not routed to its replicas.
+   */
+  getPartitionKeyIndexes(): Array<number>
+  /**
+   * Whether the statement is known to be a lightweight transaction (`IF NOT EXISTS`, `IF ...`).\
+   * Only ScyllaDB reports it, it is always `false` on Cassandra.
+   */
+  isLwt(): boolean
 }
 
+/**
+ * Unprepared statement, sent as text along with its parameters.
+ *
+ * Its options mirror the ones of `PreparedStatement`, except for `getKeyspace`: the driver only learns the
+ * keyspace of a statement from the database when preparing it, so prepare the statement with
+ * `session.prepare` to read it.
+ */
 export declare class Query {
   constructor(query: string)
   setConsistency(consistency: Consistency): void
+  /** `null` when the consistency of the execution profile is used */
+  getConsistency(): Consistency | null
   setSerialConsistency(serialConsistency: SerialConsistency): void
+  getSerialConsistency(): SerialConsistency | null
   setPageSize(pageSize: number): void
+  getPageSize(): number | null
+  /** Sets the request timeout in milliseconds, `null` to use the `requestTimeout` of the execution profile, which is in seconds */
+  setRequestTimeout(timeout?: number | undefined | null): void
+  /** Request timeout in milliseconds, `null` when the `requestTimeout` of the execution profile (in seconds) is used */
+  getRequestTimeout(): number | null
+  /** Marks the statement as idempotent, which allows it to be retried or speculatively executed */
+  setIsIdempotent(isIdempotent: boolean): void
+  getIsIdempotent(): boolean
+  setTracing(tracing: boolean): void
+  getTracing(): boolean
+  /**
+   * Sets the default timestamp of the statement, in microseconds since the unix epoch.\
+   * `null` lets the database assign the timestamp.
+   */
+  setTimestamp(timestamp?: number | undefined | null): void
+  getTimestamp(): number | null
+  /** Sets the retry policy of the statement, `null` to use the one of the execution profile */
+  setRetryPolicy(retryPolicy?: RetryPolicySetting | undefined | null): void
+  getRetryPolicy(): RetryPolicySetting | null
+  /** Sets the execution profile of the statement, `null` to use the session's default one */
+  setExecutionProfile(executionProfile?: ExecutionProfileHandle | undefined | null): void
+  getExecutionProfile(): ExecutionProfileHandle | null
+  /** Records the history of every execution of the statement in `listener` */
+  setHistoryListener(listener: HistoryListener): void
+  removeHistoryListener(): void
 }
 
 export declare class ScyllaClusterData {
@@ -122,28 +356,159 @@ export declare class ScyllaClusterData {
   getKeyspaceInfo(): Record<string, ScyllaKeyspace> | null
 }
 
+/**
+ * Iterator over the rows of a multi-page result.
+ *
+ * Pages are fetched lazily: the next page is only requested from the database once the rows of the
+ * current one were consumed, so iterating a large table never holds more than a couple of pages in memory.
+ *
+ * The iterator implements the async iterator protocol, so it can be consumed with `for await`.
+ *
+ * # Example
+ * ```javascript
+ * const rows = await session.executeIter("SELECT * FROM users");
+ *
+ * for await (const row of rows) {
+ *   console.log(row);
+ * }
+ * ```
+ */
+export declare class ScyllaRowIterator implements AsyncIterable<JSQueryRow> {
+  [Symbol.asyncIterator](): AsyncIterator<JSQueryRow>
+  /**
+   * Returns the rows of the current page, fetching the next page if the current one is exhausted.\
+   * Rows of the following page are included when it was already received. Resolves to `null` once every
+   * row was consumed.
+   */
+  nextPage(): Promise<Array<JSQueryRow> | null>
+}
+
 export declare class ScyllaSession {
   metrics(): Metrics
   getClusterData(): Promise<ScyllaClusterData>
-  executeWithTracing(query: string | Query | PreparedStatement, parameters?: Array<ParameterWithMapType> | undefined | null, options?: QueryOptions | undefined | null): Promise<TracingReturn>
+  executeWithTracing(query: string | Query | PreparedStatement, parameters?: JSQueryParameters | undefined | null, options?: QueryOptions | undefined | null): Promise<TracingReturn>
   /**
    * Sends a query to the database and receives a response.\
-   * Returns only a single page of results, to receive multiple pages use (TODO: Not implemented yet)
+   * Returns only a single page of results, to receive multiple pages use `executeIter`
    *
    * This is the easiest way to make a query, but performance is worse than that of prepared queries.
    *
    * It is discouraged to use this method with non-empty values argument. In such case, query first needs to be prepared (on a single connection), so
    * driver will perform 2 round trips instead of 1. Please use `PreparedStatement` object or `{ prepared: true }` option instead.
    *
+   * Options such as the consistency or the timeout can be set for a single call, without building a `Query`:
+   * ```javascript
+   * await session.execute("SELECT * FROM users", [], { consistency: Consistency.One, timeout: 2000 });
+   * ```
+   *
+   * Parameters are bound in order when given as an array, or by name when given as an object, in which
+   * case each key is matched against the name of a bind marker. Like CQL identifiers, names are case
+   * insensitive unless the marker is quoted (`:"userId"`):
+   * ```javascript
+   * await session.execute("SELECT * FROM users WHERE id = :userId", { userId }, { prepare: true });
+   * ```
+   *
+   * A wrong number of parameters, or a value that cannot be converted to the type of its bind marker, is
+   * rejected with a `SerializationError` naming the parameter, the expected CQL type and the received JS type.
+   *
    * # Notes
    *
    * ## UDT
-   * Order of fields in the object must match the order of fields as defined in the UDT. The
-   * driver does not check it by itself, so incorrect data will be written if the order is
-   * wrong.
+   * Fields of an object bound to a UDT are matched by name, in any order. Missing fields are sent as
+   * `null`, and a field the type does not have is rejected with a `SerializationError`.
+   */
+  execute(query: string | Query | PreparedStatement, parameters?: JSQueryParameters | undefined | null, options?: QueryOptions | undefined | null): Promise<JSQueryResult>
+  /**
+   * Sends a query to the database and returns an iterator over all the rows of the result.\
+   * Unlike `execute`, the result is not limited to a single page: pages are fetched on demand, as the
+   * iterator is consumed.
+   *
+   * The page size can be configured through `Query.setPageSize`, it defaults to 5000 rows.
+   *
+   * # Example
+   * ```javascript
+   * const rows = await session.executeIter("SELECT * FROM users WHERE age > ?", [18], { prepare: true });
+   *
+   * for await (const row of rows) {
+   *   console.log(row);
+   * }
+   * ```
+   */
+  executeIter(query: string | Query | PreparedStatement, parameters?: JSQueryParameters | undefined | null, options?: QueryOptions | undefined | null): Promise<ScyllaRowIterator>
+  /**
+   * Sends a query to the database and receives a single page of the result, along with the paging state
+   * needed to fetch the next one.
+   *
+   * The paging state is an opaque `Buffer`, it can be stored or sent to a client and handed back later to
+   * resume reading from where the previous page stopped. It is absent once the last page was fetched.
+   *
+   * Every option of `execute` applies as well, the page size defaults to the one of the statement, or to
+   * 5000 rows.
+   *
+   * # Example
+   * ```javascript
+   * let pagingState = undefined;
+   *
+   * do {
+   *   const page = await session.executePaged("SELECT * FROM users", [], { pageSize: 100, pagingState });
+   *   console.log(page.rows);
+   *   pagingState = page.pagingState;
+   * } while (pagingState);
+   * ```
+   */
+  executePaged(query: string | Query | PreparedStatement, parameters?: JSQueryParameters | undefined | null, options?: PagedQueryOptions | undefined | null): Promise<PagedResult>
+  query(scyllaQuery: Query, parameters?: JSQueryParameters | undefined | null, options?: QueryOptions | undefined | null): Promise<JSQueryResult>
+  /**
+   * Executes the same statement once for each row of parameters, keeping up to `concurrency` requests in
+   * flight. Failures do not reject the promise, they are counted and returned along with the index of their row.
+   *
+   * `rows` can be any iterable or async iterable, such as a generator reading a file. Rows are read in
+   * chunks of `concurrency` rows, so they are never all held in memory: a chunk is sent once the previous
+   * one completed, while the next one is being read. This is done in `scripts/fix-files.mjs`, the native
+   * method receives the chunks.
+   *
+   * # Example
+   * ```javascript
+   * const insert = await session.prepare("INSERT INTO users (id, name) VALUES (?, ?)");
+   * const { succeeded, failed, errors } = await session.executeConcurrent(insert, rows, { concurrency: 256 });
+   * ```
+   */
+  executeConcurrent(statement: string | PreparedStatement, rows: Iterable<JSQueryParameters> | AsyncIterable<JSQueryParameters>, concurrentOptions?: ConcurrentOptions | undefined | null, options?: QueryOptions | undefined | null): Promise<ConcurrentResult>
+  /**
+   * Writes the rows with the given statement, grouping the rows of a same partition in unlogged batches.
+   *
+   * Each batch only targets a single partition, so it is sent straight to one of its replicas and does not
+   * burden a coordinator with writes to other partitions. The statement must bind every column of the
+   * partition key.
+   *
+   * The rows of a batch share the same write timestamp, so rows writing the same primary key are put in
+   * different batches, and the batches of a partition are sent one after the other: the last row of the
+   * input wins, as if the rows were written one by one. This does not hold when `timestamp` is set in
+   * `options`, every batch then has the same timestamp.
+   *
+   * # Example
+   * ```javascript
+   * const insert = await session.prepare("INSERT INTO events (device, at, value) VALUES (?, ?, ?)");
+   * const { batches, failed } = await session.batchByPartition(insert, rows, { maxBatchSize: 50 });
+   * ```
    */
-  execute(query: string | Query | PreparedStatement, parameters?: Array<ParameterWithMapType> | undefined | null, options?: QueryOptions | undefined | null): Promise<JSQueryResult>
-  query(scyllaQuery: Query, parameters?: Array<ParameterWithMapType> | undefined | null): Promise<JSQueryResult>
+  batchByPartition(statement: string | PreparedStatement, rows: Array<JSQueryParameters>, batchOptions?: PartitionBatchOptions | undefined | null, options?: QueryOptions | undefined | null): Promise<PartitionBatchResult>
+  /**
+   * Executes a conditional statement (`INSERT ... IF NOT EXISTS`, `UPDATE ... IF ...`) and tells whether it
+   * was applied, along with the existing values when it was not.\
+   * Statements without an `IF` condition are rejected before being sent.
+   *
+   * # Example
+   * ```javascript
+   * const { applied, existing } = await session.executeConditional(
+   *   "INSERT INTO users (id, name) VALUES (?, ?) IF NOT EXISTS",
+   *   [id, "Alice"],
+   *   { prepare: true },
+   * );
+   * if (!applied) console.log(`Already taken by ${existing?.name}`);
+   * ```
+   */
+  executeConditional(query: string | Query | PreparedStatement, parameters?: JSQueryParameters | undefined | null, options?: QueryOptions | undefined | null): Promise<ConditionalResult>
   prepare(query: string): Promise<PreparedStatement>
   /**
    * Perform a batch query\
@@ -157,6 +522,7 @@ export declare class ScyllaSession {
    * # Arguments
    * * `batch` - Batch to be performed
    * * `values` - List of values for each query, it's the easiest to use an array of arrays
+   * * `options` - Options of the request, `prepare` and `pageSize` do not apply to batches
    *
    * # Example
    * ```javascript
@@ -182,7 +548,13 @@ export declare class ScyllaSession {
    * console.log(await session.execute("SELECT * FROM users"));
    * ```
    */
-  batch(batch: BatchStatement, parameters: Array<Array<ParameterWithMapType> | undefined | null>): Promise<JSQueryResult>
+  batch(batch: BatchStatement, parameters: Array<JSQueryParameters | undefined | null>, options?: QueryOptions | undefined | null): Promise<JSQueryResult>
+  /**
+   * Performs a batch of conditional statements, which are applied only if all of their conditions hold.\
+   * When the batch is not applied, `existing` holds the values of the first row returned by the database.\
+   * Batches without any conditional statement are rejected before being sent.
+   */
+  batchConditional(batch: BatchStatement, parameters: Array<JSQueryParameters | undefined | null>, options?: QueryOptions | undefined | null): Promise<ConditionalResult>
   /**
    * Sends `USE <keyspace_name>` request on all connections\
    * This allows to write `SELECT * FROM table` instead of `SELECT * FROM keyspace.table`\
@@ -259,6 +631,30 @@ export declare class Set<T = NativeTypes> {
   toString(): string
 }
 
+/** A tuple of any CqlType, elements can be `null` */
+export declare class Tuple<T = NativeTypes> {
+  constructor(values: Array<T | null>)
+  toString(): string
+}
+
+/**
+ * Marks a bound value as unset: the column is left untouched instead of being overwritten with `null`, which
+ * avoids creating tombstones when only some columns of a prepared statement are updated.
+ *
+ * Use the exported `UNSET` constant rather than creating new instances.
+ *
+ * # Example
+ * ```javascript
+ * const update = await session.prepare("UPDATE users SET name = ?, email = ? WHERE id = ?");
+ *
+ * await session.execute(update, ["Alice", UNSET, id]);
+ * ```
+ */
+export declare class Unset {
+  constructor()
+  toString(): string
+}
+
 export declare class Uuid {
   /** Generates a random UUID v4. */
   static randomV4(): Uuid
@@ -295,7 +691,21 @@ export interface Auth {
   password: string
 }
 
+/** Kind of batch, see the [CQL documentation](https://opensource.docs.scylladb.com/stable/cql/dml.html#batch-statement) */
+export declare const enum BatchType {
+  /**
+   * Guarantees that either all or none of the statements are applied, at the cost of writing the batch to
+   * a batch log first
+   */
+  Logged = 0,
+  /** Skips the batch log, cheaper when all the statements target the same partition */
+  Unlogged = 1,
+  /** Batch of counter updates, the only kind allowed to contain them */
+  Counter = 2
+}
+
 export interface ClusterConfig {
+  /** Contact points used to discover the cluster, as `host:port` (the port defaults to 9042). All of them are tried when connecting. */
   nodes: Array<string>
   compression?: Compression
   defaultExecutionProfile?: ExecutionProfile
@@ -306,6 +716,17 @@ export interface ClusterConfig {
   autoAwaitSchemaAgreement?: boolean
   /** If the schema is not agreed upon, the driver sleeps for a duration in seconds before checking it again. The default value is 0.2 (200 milliseconds) */
   schemaAgreementInterval?: number
+  /** Maximum number of statements prepared through the `prepare` option kept by each session, the least recently used ones are evicted first. Defaults to 512, 0 disables the cache */
+  preparedStatementCacheSize?: number
+}
+
+/** Specification of a bind marker or of a result column of a prepared statement */
+export interface ColumnSpecification {
+  name: string
+  keyspace: string
+  table: string
+  /** Type as written in CQL, such as `int` or `map<text, list<int>>` */
+  type: string
 }
 
 export declare const enum Compression {
@@ -314,6 +735,48 @@ export declare const enum Compression {
   Snappy = 2
 }
 
+/** Failure of a single row of `executeConcurrent` */
+export interface ConcurrentError {
+  /** Index of the row in the given rows */
+  index: number
+  /** Name of the error class the failure would have been thrown as, such as `WriteTimeoutError` */
+  name: string
+  message: string
+}
+
+export interface ConcurrentOptions {
+  /** Maximum number of requests in flight at the same time, defaults to 100 */
+  concurrency?: number
+  /**
+   * Stops sending the remaining rows after the first failure, defaults to `false`.\
+   * Requests already in flight are still awaited and counted.
+   */
+  stopOnError?: boolean
+}
+
+export interface ConcurrentResult {
+  succeeded: number
+  failed: number
+  /**
+   * Number of rows that were never sent because of `stopOnError`. Rows left in an iterator are not read:
+   * only the chunk already read ahead is counted
+   */
+  unsent: number
+  /** Failures ordered by the index of their row */
+  errors: Array<ConcurrentError>
+}
+
+/** Result of a conditional statement (`INSERT ... IF NOT EXISTS`, `UPDATE ... IF ...`) or of a batch of them */
+export interface ConditionalResult {
+  /** Whether the condition held and the statement was applied */
+  applied: boolean
+  /**
+   * When the statement was not applied, the values that made the condition fail: the existing row for
+   * `IF NOT EXISTS`, the columns checked by the condition otherwise
+   */
+  existing?: JSQueryRow
+}
+
 export interface ConnectionOptions {
   keyspace?: string
   auth?: Auth
@@ -334,10 +797,66 @@ export declare const enum Consistency {
   LocalSerial = 9
 }
 
+export interface CustomRetryPolicyOptions {
+  /**
+   * How long a failed request waits for the callback to decide, in milliseconds, defaults to 1000.\
+   * The error is returned without retrying when the callback does not answer in time.
+   */
+  decisionTimeout?: number
+}
+
 export interface ExecutionProfile {
   consistency?: Consistency
   serialConsistency?: SerialConsistency
+  /** Request timeout in seconds, unlike the timeouts of `Query`, `PreparedStatement` and `QueryOptions` which are in milliseconds. Defaults to 30 seconds */
   requestTimeout?: number
+  /** Defaults to a token aware policy without any preferred datacenter */
+  loadBalancing?: LoadBalancingConfig
+  /** Defaults to `RetryPolicy.Default` */
+  retryPolicy?: RetryPolicySetting
+  /** Disabled by default */
+  speculativeExecution?: SpeculativeExecutionConfig
+}
+
+export interface LatencyAwarenessConfig {
+  /** How many times slower than the fastest node a node must be to be penalised, defaults to 2 */
+  exclusionThreshold?: number
+  /** How long a node stays penalised, in milliseconds, defaults to 10 seconds */
+  retryPeriod?: number
+  /** How often the fastest average latency is computed again, in milliseconds, defaults to 100 */
+  updateRate?: number
+  /** Number of measurements needed before a node can be penalised, defaults to 50 */
+  minimumMeasurements?: number
+  /** Weight given to older latencies in the average, in milliseconds, defaults to 100 */
+  scale?: number
+}
+
+/**
+ * Settings of the default load balancing policy, which decides the nodes a request is sent to and in which
+ * order they are tried.
+ */
+export interface LoadBalancingConfig {
+  /** Datacenter whose nodes are tried before the ones of other datacenters, usually the local one */
+  preferredDatacenter?: string
+  /** Rack of the preferred datacenter whose nodes are tried first, ignored without `preferredDatacenter` */
+  preferredRack?: string
+  /** Sends requests to the replicas of the data they target first, defaults to `true` */
+  tokenAware?: boolean
+  /**
+   * Allows sending requests to nodes outside of the preferred datacenter when the consistency permits it,
+   * defaults to `false`
+   */
+  permitDcFailover?: boolean
+  /**
+   * Spreads the load over the replicas by trying them in a random order, defaults to `true`.\
+   * Disabling it makes a better use of the caches of the database, at the cost of an uneven load.
+   */
+  enableShufflingReplicas?: boolean
+  /**
+   * Moves the nodes that answer much slower than the others to the end of the query plans.\
+   * Only enable it when benchmarks prove it beneficial to the workload.
+   */
+  latencyAwareness?: LatencyAwarenessConfig
 }
 
 export interface NetworkTopologyStrategy {
@@ -349,8 +868,103 @@ export interface Other {
   data: Record<string, string>
 }
 
+/** A single page of a result, along with the state needed to fetch the following one */
+export interface PagedResult {
+  rows: Array<JSQueryRow>
+  /**
+   * Opaque paging state to be handed back to `executePaged` to fetch the next page.\
+   * It is absent when the current page is the last one.
+   */
+  pagingState?: Buffer
+}
+
+/** Failure of a batch of `batchByPartition`, or of a single row whose partition key could not be computed */
+export interface PartitionBatchError {
+  /** Indexes of the rows of the batch in the given array */
+  rows: Array<number>
+  /** Name of the error class the failure would have been thrown as, such as `WriteTimeoutError` */
+  name: string
+  message: string
+}
+
+export interface PartitionBatchOptions {
+  /** Maximum number of rows in a single batch, larger partitions are split into several batches. Defaults to 100 */
+  maxBatchSize?: number
+  /** Maximum number of batches in flight at the same time, defaults to 100 */
+  concurrency?: number
+}
+
+export interface PartitionBatchResult {
+  /** Number of batches sent */
+  batches: number
+  /** Number of rows written */
+  succeeded: number
+  /** Number of rows that were not written */
+  failed: number
+  /** Failures ordered by the index of their first row */
+  errors: Array<PartitionBatchError>
+}
+
+/** Options of a single request, they take precedence over the ones set on the statement and the execution profile */
 export interface QueryOptions {
+  /**
+   * Prepares the statement before executing it. Prepared statements are cached by the session, so a given
+   * statement is only prepared once
+   */
   prepare?: boolean
+  consistency?: Consistency
+  serialConsistency?: SerialConsistency
+  /** Request timeout in milliseconds, overriding the `requestTimeout` of the execution profile, which is in seconds */
+  timeout?: number
+  /** Number of rows per page, ignored by batches */
+  pageSize?: number
+  /** Default timestamp of the request, in microseconds since the unix epoch */
+  timestamp?: number
+  /** Marks the request as idempotent, which allows it to be retried or speculatively executed */
+  isIdempotent?: boolean
+  tracing?: boolean
+  /** Execution profile used for this request instead of the session's default one */
+  executionProfile?: ExecutionProfileHandle
+}
+
+/** What to do with a failed request, returned by the callback of a `CustomRetryPolicy`. */
+export declare const enum RetryDecision {
+  /** Sends the request again to the same node */
+  RetrySameNode = 0,
+  /** Sends the request to the next node of the query plan */
+  RetryNextNode = 1,
+  /** Drops the error and returns an empty result, only meant for writes */
+  Ignore = 2,
+  /** Returns the error without retrying */
+  Rethrow = 3
+}
+
+/** Failed attempt passed to the callback of a `CustomRetryPolicy`. */
+export interface RetryInfo {
+  /** Name of the error class the failure would be thrown as, e.g. `ReadTimeoutError` */
+  errorName: string
+  message: string
+  /** Number of attempts that failed so far for this request, starting at 1 */
+  attempt: number
+  /** `false` when it is unknown whether the request is idempotent */
+  isIdempotent: boolean
+  consistency: Consistency
+}
+
+/** Policy deciding whether a failed request is retried, and on which node. */
+export declare const enum RetryPolicy {
+  /**
+   * Retries when there is a high chance of success, and only idempotent requests when it could lead to
+   * writing the same data twice
+   */
+  Default = 0,
+  /** Never retries, errors are returned as soon as they happen */
+  Fallthrough = 1,
+  /**
+   * Like `Default`, but also retries at a lower consistency level when not enough replicas answered.\
+   * Use it only when reading or writing at a lower consistency than requested is acceptable.
+   */
+  DowngradingConsistency = 2
 }
 
 export interface ScyllaKeyspace {
@@ -385,6 +999,28 @@ export interface SimpleStrategy {
   replicationFactor: number
 }
 
+/**
+ * Settings of speculative execution, which sends a request to other nodes as well when the first one is slow
+ * to answer, and keeps the first answer received.
+ *
+ * Only idempotent statements are executed speculatively, see `setIsIdempotent`.
+ */
+export interface SpeculativeExecutionConfig {
+  /** Maximum number of requests sent in addition to the first one */
+  maxRetryCount: number
+  /**
+   * Delay between each request, in milliseconds, defaults to 100.\
+   * Ignored when `percentile` is set.
+   */
+  retryInterval?: number
+  /**
+   * Sends another request once the current one is slower than this percentile of the latencies measured
+   * by the session, e.g. `99.0`, instead of waiting for a fixed delay.\
+   * Must be greater than 0 and at most 100.
+   */
+  percentile?: number
+}
+
 export interface Ssl {
   enabled: boolean
   caFilepath?: string
@@ -398,11 +1034,28 @@ export declare const enum VerifyMode {
   Peer = 1
 }
 
-type NativeTypes = number | string | Uuid | bigint | Duration | Decimal | Float | List;
-type WithMapType = NativeTypes | Record<string, NativeTypes> | NativeTypes[];
-type ParameterWithMapType = WithMapType;
-type JSQueryResult = Record<string, WithMapType>[];
+type NativeTypes = number | string | Uuid | bigint | Duration | Decimal | Float | List | Buffer | Date | LocalDate | LocalTime;
+type WithMapType =
+  | NativeTypes
+  | { [key: string]: WithMapType | null }
+  | Array<WithMapType | null>
+  | globalThis.Map<WithMapType, WithMapType>;
+type ParameterWithMapType = WithMapType | Tuple | Uint8Array | ArrayBuffer;
+type ParameterValue = ParameterWithMapType | Unset | null | undefined;
+type JSQueryParameters = ParameterValue[] | Record<string, ParameterValue>;
+type JSQueryRow = Record<string, WithMapType | null>;
+type JSQueryResult = JSQueryRow[];
 type TracingReturn = { result: JSQueryResult; tracing: TracingInfo };
+type RetryPolicySetting = RetryPolicy | CustomRetryPolicy;
+
+/** Leaves a column untouched when bound as a parameter, see `Unset` */
+export declare const UNSET: Unset;
+
+/** Options of `executePaged`, every option of `QueryOptions` along with the paging state */
+export interface PagedQueryOptions extends QueryOptions {
+  /** Paging state returned by a previous call, omit it to fetch the first page */
+  pagingState?: Buffer;
+}
 
 export interface TracingInfo {
   client?: string; // IP address as a string
@@ -427,4 +1080,52 @@ export interface TracingEvent {
   source?: string; // IP address as a string
   source_elapsed?: number;
   thread?: string;
-}
\ No newline at end of file
+}
+
+/** Base class of every error thrown by the driver */
+export declare class ScyllaError extends Error {}
+export declare class ReadTimeoutError extends ScyllaError {
+  consistency: Consistency;
+  received: number;
+  required: number;
+  dataPresent: boolean;
+}
+export declare class WriteTimeoutError extends ScyllaError {
+  consistency: Consistency;
+  received: number;
+  required: number;
+  writeType: string;
+}
+export declare class ReadFailureError extends ScyllaError {
+  consistency: Consistency;
+  received: number;
+  required: number;
+  numFailures: number;
+  dataPresent: boolean;
+}
+export declare class WriteFailureError extends ScyllaError {
+  consistency: Consistency;
+  received: number;
+  required: number;
+  numFailures: number;
+  writeType: string;
+}
+export declare class UnavailableError extends ScyllaError {
+  consistency: Consistency;
+  required: number;
+  alive: number;
+}
+export declare class OverloadedError extends ScyllaError {}
+/** Named so that it does not shadow the global `SyntaxError` */
+export declare class CqlSyntaxError extends ScyllaError {}
+export declare class InvalidQueryError extends ScyllaError {}
+export declare class AlreadyExistsError extends ScyllaError {
+  keyspace: string;
+  /** Absent when the keyspace itself already exists */
+  table?: string;
+}
+export declare class AuthenticationError extends ScyllaError {}
+export declare class UnauthorizedError extends ScyllaError {}
+export declare class ConnectionError extends ScyllaError {}
+export declare class RequestTimeoutError extends ScyllaError {}
+export declare class SerializationError extends ScyllaError {}
\ No newline at end of file
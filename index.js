@@ -368,22 +368,33 @@ module.exports.BatchStatement = nativeBinding.BatchStatement
 module.exports.ScyllaBatchStatement = nativeBinding.ScyllaBatchStatement
 module.exports.Cluster = nativeBinding.Cluster
 module.exports.ScyllaCluster = nativeBinding.ScyllaCluster
+module.exports.CustomRetryPolicy = nativeBinding.CustomRetryPolicy
 module.exports.Decimal = nativeBinding.Decimal
 module.exports.Double = nativeBinding.Double
 module.exports.Duration = nativeBinding.Duration
+module.exports.ExecutionProfileHandle = nativeBinding.ExecutionProfileHandle
 module.exports.Float = nativeBinding.Float
+module.exports.HistoryListener = nativeBinding.HistoryListener
 module.exports.List = nativeBinding.List
+module.exports.LocalDate = nativeBinding.LocalDate
+module.exports.LocalTime = nativeBinding.LocalTime
 module.exports.Map = nativeBinding.Map
 module.exports.Metrics = nativeBinding.Metrics
 module.exports.PreparedStatement = nativeBinding.PreparedStatement
 module.exports.Query = nativeBinding.Query
 module.exports.ScyllaClusterData = nativeBinding.ScyllaClusterData
+module.exports.ScyllaRowIterator = nativeBinding.ScyllaRowIterator
 module.exports.ScyllaSession = nativeBinding.ScyllaSession
 module.exports.Set = nativeBinding.Set
+module.exports.Tuple = nativeBinding.Tuple
+module.exports.Unset = nativeBinding.Unset
 module.exports.Uuid = nativeBinding.Uuid
 module.exports.Varint = nativeBinding.Varint
+module.exports.BatchType = nativeBinding.BatchType
 module.exports.Compression = nativeBinding.Compression
 module.exports.Consistency = nativeBinding.Consistency
+module.exports.RetryDecision = nativeBinding.RetryDecision
+module.exports.RetryPolicy = nativeBinding.RetryPolicy
 module.exports.SerialConsistency = nativeBinding.SerialConsistency
 module.exports.VerifyMode = nativeBinding.VerifyMode
 
@@ -397,4 +408,128 @@ nativeBinding.Double.prototype[customInspectSymbol]  = function () { return this
 nativeBinding.List.prototype[customInspectSymbol]  = function () { return this.toString(); }
 nativeBinding.Set.prototype[customInspectSymbol]  = function () { return this.toString(); }
 nativeBinding.Map.prototype[customInspectSymbol]  = function () { return this.toString(); }
-nativeBinding.Varint.prototype[customInspectSymbol]  = function () { return this.toString(); }
\ No newline at end of file
+nativeBinding.Tuple.prototype[customInspectSymbol]  = function () { return this.toString(); }
+nativeBinding.Varint.prototype[customInspectSymbol]  = function () { return this.toString(); }
+nativeBinding.LocalDate.prototype[customInspectSymbol]  = function () { return this.toString(); }
+nativeBinding.LocalTime.prototype[customInspectSymbol]  = function () { return this.toString(); }
+
+nativeBinding.ScyllaRowIterator.prototype[Symbol.asyncIterator] = async function* () {
+  let page;
+  while ((page = await this.nextPage()) !== null) yield* page;
+}
+
+// Reads the rows in chunks of `concurrency` rows, the native method only receives arrays. A chunk is only sent
+// once the previous one completed, so that at most `concurrency` requests are in flight and nothing is sent
+// after a failure with `stopOnError`. The next chunk is read while the current one is being sent
+const executeConcurrentChunk = nativeBinding.ScyllaSession.prototype.executeConcurrent;
+nativeBinding.ScyllaSession.prototype.executeConcurrent = async function (statement, rows, concurrentOptions, options) {
+  const concurrency = Math.max(1, concurrentOptions?.concurrency ?? 100);
+  const chunkOptions = { ...concurrentOptions, concurrency };
+  const iterator = rows[Symbol.asyncIterator]?.() ?? rows[Symbol.iterator]();
+  const total = { succeeded: 0, failed: 0, unsent: 0, errors: [] };
+  let done = false;
+  let stopped = false;
+
+  const readChunk = async () => {
+    const chunk = [];
+    while (!done && chunk.length < concurrency) {
+      const next = await iterator.next();
+      if (!(done = next.done)) chunk.push(next.value);
+    }
+    return chunk;
+  };
+
+  try {
+    let offset = 0;
+    let chunk = await readChunk();
+    while (chunk.length > 0) {
+      const [sent, read] = await Promise.allSettled([
+        executeConcurrentChunk.call(this, statement, chunk, chunkOptions, options),
+        readChunk(),
+      ]);
+      if (sent.status === "rejected") throw sent.reason;
+      if (read.status === "rejected") throw read.reason;
+
+      const result = sent.value;
+      total.succeeded += result.succeeded;
+      total.failed += result.failed;
+      total.unsent += result.unsent;
+      for (const error of result.errors) total.errors.push({ ...error, index: error.index + offset });
+
+      offset += chunk.length;
+      chunk = read.value;
+      if (concurrentOptions?.stopOnError && result.failed > 0) {
+        stopped = true;
+        total.unsent += chunk.length;
+        break;
+      }
+    }
+  } finally {
+    if (!done) await iterator.return?.();
+  }
+
+  if (stopped && Array.isArray(rows)) total.unsent = rows.length - total.succeeded - total.failed;
+  total.errors.sort((a, b) => a.index - b.index);
+  return total;
+};
+
+module.exports.UNSET = Object.freeze(new nativeBinding.Unset())
+
+class ScyllaError extends Error {
+  constructor(message, details) {
+    super(message);
+    this.name = this.constructor.name;
+    Object.assign(this, details);
+  }
+}
+
+const errorClasses = { ScyllaError };
+for (const name of [
+  "ReadTimeoutError",
+  "WriteTimeoutError",
+  "ReadFailureError",
+  "WriteFailureError",
+  "UnavailableError",
+  "OverloadedError",
+  "CqlSyntaxError",
+  "InvalidQueryError",
+  "AlreadyExistsError",
+  "AuthenticationError",
+  "UnauthorizedError",
+  "ConnectionError",
+  "RequestTimeoutError",
+  "SerializationError",
+]) {
+  errorClasses[name] = { [name]: class extends ScyllaError {} }[name];
+  module.exports[name] = errorClasses[name];
+}
+module.exports.ScyllaError = ScyllaError;
+
+function toTypedError(error) {
+  if (!(error instanceof Error) || !error.message.startsWith("{")) return error;
+
+  try {
+    const { name, message, ...details } = JSON.parse(error.message);
+    return name in errorClasses ? new errorClasses[name](message, details) : error;
+  } catch {
+    return error;
+  }
+}
+
+// Only the classes sending requests can fail with a typed error, the others are left untouched
+for (const binding of [nativeBinding.Cluster, nativeBinding.ScyllaSession, nativeBinding.ScyllaRowIterator]) {
+  for (const key of Object.getOwnPropertyNames(binding.prototype)) {
+    const { value: method } = Object.getOwnPropertyDescriptor(binding.prototype, key);
+    if (key === "constructor" || typeof method !== "function") continue;
+
+    binding.prototype[key] = function (...args) {
+      let result;
+      try {
+        result = method.apply(this, args);
+      } catch (error) {
+        throw toTypedError(error);
+      }
+      return result instanceof Promise ? result.catch((error) => Promise.reject(toTypedError(error))) : result;
+    };
+  }
+}
\ No newline at end of file
@@ -5,7 +5,12 @@ function addGenericTypes(filename) {
   const updatedContent = content
     .replace(/export declare class List\b(.*){/, "export declare class List<T = NativeTypes>$1{")
     .replace(/export declare class Map\b(.*){/, "export declare class Map<T = NativeTypes, U = NativeTypes>$1{")
-    .replace(/export declare class Set\b(.*){/, "export declare class Set<T = NativeTypes>$1{");
+    .replace(/export declare class Set\b(.*){/, "export declare class Set<T = NativeTypes>$1{")
+    .replace(/export declare class Tuple\b(.*){/, "export declare class Tuple<T = NativeTypes>$1{")
+    .replace(
+      /export declare class ScyllaRowIterator\b(.*){/,
+      "export declare class ScyllaRowIterator$1implements AsyncIterable<JSQueryRow> {\n  [Symbol.asyncIterator](): AsyncIterator<JSQueryRow>",
+    );
 
   writeFileSync(filename, updatedContent);
 }
@@ -27,7 +32,230 @@ nativeBinding.Double.prototype[customInspectSymbol]  = function () { return this
 nativeBinding.List.prototype[customInspectSymbol]  = function () { return this.toString(); }
 nativeBinding.Set.prototype[customInspectSymbol]  = function () { return this.toString(); }
 nativeBinding.Map.prototype[customInspectSymbol]  = function () { return this.toString(); }
+nativeBinding.Tuple.prototype[customInspectSymbol]  = function () { return this.toString(); }
 nativeBinding.Varint.prototype[customInspectSymbol]  = function () { return this.toString(); }
+nativeBinding.LocalDate.prototype[customInspectSymbol]  = function () { return this.toString(); }
+nativeBinding.LocalTime.prototype[customInspectSymbol]  = function () { return this.toString(); }
+`,
+      )
+      .trim(),
+  );
+}
+
+// Make the native iterators consumable with `for await`
+function addAsyncIterators(filename) {
+  writeFileSync(
+    filename,
+    readFileSync(filename, "utf8")
+      .concat(
+        `
+
+nativeBinding.ScyllaRowIterator.prototype[Symbol.asyncIterator] = async function* () {
+  let page;
+  while ((page = await this.nextPage()) !== null) yield* page;
+}
+
+// Reads the rows in chunks of \`concurrency\` rows, the native method only receives arrays. A chunk is only sent
+// once the previous one completed, so that at most \`concurrency\` requests are in flight and nothing is sent
+// after a failure with \`stopOnError\`. The next chunk is read while the current one is being sent
+const executeConcurrentChunk = nativeBinding.ScyllaSession.prototype.executeConcurrent;
+nativeBinding.ScyllaSession.prototype.executeConcurrent = async function (statement, rows, concurrentOptions, options) {
+  const concurrency = Math.max(1, concurrentOptions?.concurrency ?? 100);
+  const chunkOptions = { ...concurrentOptions, concurrency };
+  const iterator = rows[Symbol.asyncIterator]?.() ?? rows[Symbol.iterator]();
+  const total = { succeeded: 0, failed: 0, unsent: 0, errors: [] };
+  let done = false;
+  let stopped = false;
+
+  const readChunk = async () => {
+    const chunk = [];
+    while (!done && chunk.length < concurrency) {
+      const next = await iterator.next();
+      if (!(done = next.done)) chunk.push(next.value);
+    }
+    return chunk;
+  };
+
+  try {
+    let offset = 0;
+    let chunk = await readChunk();
+    while (chunk.length > 0) {
+      const [sent, read] = await Promise.allSettled([
+        executeConcurrentChunk.call(this, statement, chunk, chunkOptions, options),
+        readChunk(),
+      ]);
+      if (sent.status === "rejected") throw sent.reason;
+      if (read.status === "rejected") throw read.reason;
+
+      const result = sent.value;
+      total.succeeded += result.succeeded;
+      total.failed += result.failed;
+      total.unsent += result.unsent;
+      for (const error of result.errors) total.errors.push({ ...error, index: error.index + offset });
+
+      offset += chunk.length;
+      chunk = read.value;
+      if (concurrentOptions?.stopOnError && result.failed > 0) {
+        stopped = true;
+        total.unsent += chunk.length;
+        break;
+      }
+    }
+  } finally {
+    if (!done) await iterator.return?.();
+  }
+
+  if (stopped && Array.isArray(rows)) total.unsent = rows.length - total.succeeded - total.failed;
+  total.errors.sort((a, b) => a.index - b.index);
+  return total;
+};
+`,
+      )
+      .trim(),
+  );
+}
+
+function addConstants(filename) {
+  writeFileSync(
+    filename,
+    readFileSync(filename, "utf8")
+      .concat(
+        `
+
+module.exports.UNSET = Object.freeze(new nativeBinding.Unset())
+`,
+      )
+      .trim(),
+  );
+}
+
+// Native errors carry a serialized description of the failure (see `src/error.rs`), rebuild them as
+// instances of typed error classes so they can be told apart with `instanceof`
+function addErrorClasses(filename) {
+  writeFileSync(
+    filename,
+    readFileSync(filename, "utf8")
+      .concat(
+        `
+
+class ScyllaError extends Error {
+  constructor(message, details) {
+    super(message);
+    this.name = this.constructor.name;
+    Object.assign(this, details);
+  }
+}
+
+const errorClasses = { ScyllaError };
+for (const name of [
+  "ReadTimeoutError",
+  "WriteTimeoutError",
+  "ReadFailureError",
+  "WriteFailureError",
+  "UnavailableError",
+  "OverloadedError",
+  "CqlSyntaxError",
+  "InvalidQueryError",
+  "AlreadyExistsError",
+  "AuthenticationError",
+  "UnauthorizedError",
+  "ConnectionError",
+  "RequestTimeoutError",
+  "SerializationError",
+]) {
+  errorClasses[name] = { [name]: class extends ScyllaError {} }[name];
+  module.exports[name] = errorClasses[name];
+}
+module.exports.ScyllaError = ScyllaError;
+
+function toTypedError(error) {
+  if (!(error instanceof Error) || !error.message.startsWith("{")) return error;
+
+  try {
+    const { name, message, ...details } = JSON.parse(error.message);
+    return name in errorClasses ? new errorClasses[name](message, details) : error;
+  } catch {
+    return error;
+  }
+}
+
+// Only the classes sending requests can fail with a typed error, the others are left untouched
+for (const binding of [nativeBinding.Cluster, nativeBinding.ScyllaSession, nativeBinding.ScyllaRowIterator]) {
+  for (const key of Object.getOwnPropertyNames(binding.prototype)) {
+    const { value: method } = Object.getOwnPropertyDescriptor(binding.prototype, key);
+    if (key === "constructor" || typeof method !== "function") continue;
+
+    binding.prototype[key] = function (...args) {
+      let result;
+      try {
+        result = method.apply(this, args);
+      } catch (error) {
+        throw toTypedError(error);
+      }
+      return result instanceof Promise ? result.catch((error) => Promise.reject(toTypedError(error))) : result;
+    };
+  }
+}
+`,
+      )
+      .trim(),
+  );
+}
+
+function addErrorTypes(filename) {
+  writeFileSync(
+    filename,
+    readFileSync(filename, "utf8")
+      .concat(
+        `
+
+/** Base class of every error thrown by the driver */
+export declare class ScyllaError extends Error {}
+export declare class ReadTimeoutError extends ScyllaError {
+  consistency: Consistency;
+  received: number;
+  required: number;
+  dataPresent: boolean;
+}
+export declare class WriteTimeoutError extends ScyllaError {
+  consistency: Consistency;
+  received: number;
+  required: number;
+  writeType: string;
+}
+export declare class ReadFailureError extends ScyllaError {
+  consistency: Consistency;
+  received: number;
+  required: number;
+  numFailures: number;
+  dataPresent: boolean;
+}
+export declare class WriteFailureError extends ScyllaError {
+  consistency: Consistency;
+  received: number;
+  required: number;
+  numFailures: number;
+  writeType: string;
+}
+export declare class UnavailableError extends ScyllaError {
+  consistency: Consistency;
+  required: number;
+  alive: number;
+}
+export declare class OverloadedError extends ScyllaError {}
+/** Named so that it does not shadow the global \`SyntaxError\` */
+export declare class CqlSyntaxError extends ScyllaError {}
+export declare class InvalidQueryError extends ScyllaError {}
+export declare class AlreadyExistsError extends ScyllaError {
+  keyspace: string;
+  /** Absent when the keyspace itself already exists */
+  table?: string;
+}
+export declare class AuthenticationError extends ScyllaError {}
+export declare class UnauthorizedError extends ScyllaError {}
+export declare class ConnectionError extends ScyllaError {}
+export declare class RequestTimeoutError extends ScyllaError {}
+export declare class SerializationError extends ScyllaError {}
 `,
       )
       .trim(),
@@ -40,11 +268,28 @@ function addJSQueryResultType(filename) {
     readFileSync(filename, "utf8")
       .concat(
         `
-type NativeTypes = number | string | Uuid | bigint | Duration | Decimal | Float | List;
-type WithMapType = NativeTypes | Record<string, NativeTypes> | NativeTypes[];
-type ParameterWithMapType = WithMapType;
-type JSQueryResult = Record<string, WithMapType>[];
+type NativeTypes = number | string | Uuid | bigint | Duration | Decimal | Float | List | Buffer | Date | LocalDate | LocalTime;
+type WithMapType =
+  | NativeTypes
+  | { [key: string]: WithMapType | null }
+  | Array<WithMapType | null>
+  | globalThis.Map<WithMapType, WithMapType>;
+type ParameterWithMapType = WithMapType | Tuple | Uint8Array | ArrayBuffer;
+type ParameterValue = ParameterWithMapType | Unset | null | undefined;
+type JSQueryParameters = ParameterValue[] | Record<string, ParameterValue>;
+type JSQueryRow = Record<string, WithMapType | null>;
+type JSQueryResult = JSQueryRow[];
 type TracingReturn = { result: JSQueryResult; tracing: TracingInfo };
+type RetryPolicySetting = RetryPolicy | CustomRetryPolicy;
+
+/** Leaves a column untouched when bound as a parameter, see \`Unset\` */
+export declare const UNSET: Unset;
+
+/** Options of \`executePaged\`, every option of \`QueryOptions\` along with the paging state */
+export interface PagedQueryOptions extends QueryOptions {
+  /** Paging state returned by a previous call, omit it to fetch the first page */
+  pagingState?: Buffer;
+}
 
 export interface TracingInfo {
   client?: string; // IP address as a string
@@ -77,8 +322,14 @@ export interface TracingEvent {
 }
 
 const filename = process.argv[process.argv.length - 1];
-if (filename.endsWith("index.js")) addInspector(filename);
+if (filename.endsWith("index.js")) {
+  addInspector(filename);
+  addAsyncIterators(filename);
+  addConstants(filename);
+  addErrorClasses(filename);
+}
 else if (filename.endsWith("index.d.ts")) {
   addGenericTypes(filename);
   addJSQueryResultType(filename);
+  addErrorTypes(filename);
 }
@@ -8,6 +8,7 @@ pub mod compression;
 
 #[napi(object)]
 pub struct ClusterConfig {
+  /// Contact points used to discover the cluster, as `host:port` (the port defaults to 9042). All of them are tried when connecting.
   pub nodes: Vec<String>,
   pub compression: Option<Compression>,
   pub default_execution_profile: Option<ExecutionProfile>,
@@ -20,4 +21,6 @@ pub struct ClusterConfig {
   pub auto_await_schema_agreement: Option<bool>,
   /// If the schema is not agreed upon, the driver sleeps for a duration in seconds before checking it again. The default value is 0.2 (200 milliseconds)
   pub schema_agreement_interval: Option<i32>,
+  /// Maximum number of statements prepared through the `prepare` option kept by each session, the least recently used ones are evicted first. Defaults to 512, 0 disables the cache
+  pub prepared_statement_cache_size: Option<u32>,
 }
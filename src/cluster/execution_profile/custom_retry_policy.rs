@@ -0,0 +1,228 @@
+use std::fmt::Debug;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use napi::bindgen_prelude::FromNapiValue;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{CallContext, Env, JsFunction, JsUnknown, NapiRaw, Result, Status};
+use scylla::retry_policy::{QueryInfo, RetrySession};
+
+use super::consistency::Consistency;
+use crate::error::ScyllaError;
+
+/// Default time a request waits for the callback to decide, in milliseconds
+const DEFAULT_DECISION_TIMEOUT: u32 = 1000;
+
+/// What to do with a failed request, returned by the callback of a `CustomRetryPolicy`.
+#[napi]
+pub enum RetryDecision {
+  /// Sends the request again to the same node
+  RetrySameNode,
+  /// Sends the request to the next node of the query plan
+  RetryNextNode,
+  /// Drops the error and returns an empty result, only meant for writes
+  Ignore,
+  /// Returns the error without retrying
+  Rethrow,
+}
+
+impl From<RetryDecision> for scylla::retry_policy::RetryDecision {
+  fn from(value: RetryDecision) -> Self {
+    match value {
+      RetryDecision::RetrySameNode => Self::RetrySameNode(None),
+      RetryDecision::RetryNextNode => Self::RetryNextNode(None),
+      RetryDecision::Ignore => Self::IgnoreWriteError,
+      RetryDecision::Rethrow => Self::DontRetry,
+    }
+  }
+}
+
+/// Failed attempt passed to the callback of a `CustomRetryPolicy`.
+#[napi(object)]
+pub struct RetryInfo {
+  /// Name of the error class the failure would be thrown as, e.g. `ReadTimeoutError`
+  pub error_name: String,
+  pub message: String,
+  /// Number of attempts that failed so far for this request, starting at 1
+  pub attempt: u32,
+  /// `false` when it is unknown whether the request is idempotent
+  pub is_idempotent: bool,
+  pub consistency: Consistency,
+}
+
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct CustomRetryPolicyOptions {
+  /// How long a failed request waits for the callback to decide, in milliseconds, defaults to 1000.\
+  /// The error is returned without retrying when the callback does not answer in time.
+  pub decision_timeout: Option<u32>,
+}
+
+/// Retry policy deciding from a JS callback what to do after each failed attempt.
+///
+/// When the callback throws or returns something else than a `RetryDecision`, the error is returned without
+/// retrying.
+///
+/// # Blocking
+/// The driver decides about retries synchronously, so the callback runs on the main thread while one of the
+/// driver's threads waits for its answer, up to `decisionTimeout`. The other requests keep progressing in the
+/// meantime, but every failure waiting for a decision holds a thread: the callback must be synchronous and
+/// quick, and the main thread must not be busy for long while requests fail. Prefer the policies of
+/// `RetryPolicy` when they fit.
+///
+/// # Example
+/// ```javascript
+/// const policy = new CustomRetryPolicy(({ errorName, attempt, isIdempotent }) =>
+///   errorName === "ReadTimeoutError" && isIdempotent && attempt < 3
+///     ? RetryDecision.RetryNextNode
+///     : RetryDecision.Rethrow,
+/// );
+/// query.setRetryPolicy(policy);
+/// ```
+#[napi]
+#[derive(Clone)]
+pub struct CustomRetryPolicy {
+  pub(crate) policy: JsRetryPolicy,
+}
+
+#[napi]
+impl CustomRetryPolicy {
+  #[napi(
+    constructor,
+    ts_args_type = "callback: (info: RetryInfo) => RetryDecision, options?: CustomRetryPolicyOptions"
+  )]
+  pub fn new(
+    env: Env,
+    callback: JsFunction,
+    options: Option<CustomRetryPolicyOptions>,
+  ) -> Result<Self> {
+    // Calling the callback straight from the threadsafe function would abort the process when it throws,
+    // so it goes through a wrapper catching the error, which receives the callback as a bound argument
+    let wrapper = env
+      .create_function_from_closure("retryPolicy", decide)?
+      .coerce_to_object()?;
+    let bind: JsFunction = wrapper.get_named_property("bind")?;
+    let bound: JsFunction = unsafe {
+      bind
+        .call(
+          Some(&wrapper),
+          &[env.get_undefined()?.into_unknown(), callback.into_unknown()],
+        )?
+        .cast()
+    };
+
+    let mut callback: ThreadsafeFunction<RetryInfo, ErrorStrategy::Fatal> =
+      bound.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+    // The policy must not keep the process alive on its own
+    callback.unref(&env)?;
+
+    let decision_timeout = options
+      .and_then(|options| options.decision_timeout)
+      .unwrap_or(DEFAULT_DECISION_TIMEOUT);
+
+    Ok(Self {
+      policy: JsRetryPolicy {
+        callback,
+        decision_timeout: Duration::from_millis(decision_timeout.into()),
+      },
+    })
+  }
+
+  /// Asks the callback what to do with a failed attempt the way the driver does, from another thread and
+  /// with the same timeout.\
+  /// Resolves to `null` when the callback did not answer in time.
+  #[napi]
+  pub async fn decide(&self, info: RetryInfo) -> Option<RetryDecision> {
+    let policy = self.policy.clone();
+    tokio::task::spawn_blocking(move || policy.decide(info))
+      .await
+      .ok()
+      .flatten()
+  }
+}
+
+fn decide(ctx: CallContext) -> Result<RetryDecision> {
+  let callback = ctx.get::<JsFunction>(0)?;
+  let info = ctx.get::<JsUnknown>(1)?;
+
+  Ok(
+    callback
+      .call(None, &[info])
+      .and_then(|decision| unsafe { RetryDecision::from_napi_value(ctx.env.raw(), decision.raw()) })
+      .unwrap_or(RetryDecision::Rethrow),
+  )
+}
+
+#[derive(Clone)]
+pub(crate) struct JsRetryPolicy {
+  callback: ThreadsafeFunction<RetryInfo, ErrorStrategy::Fatal>,
+  decision_timeout: Duration,
+}
+
+impl JsRetryPolicy {
+  /// Calls the callback on the main thread and blocks until it answers, `None` when it did not in time
+  fn decide(&self, info: RetryInfo) -> Option<RetryDecision> {
+    let (sender, receiver) = mpsc::sync_channel(1);
+    let status = self.callback.call_with_return_value(
+      info,
+      ThreadsafeFunctionCallMode::NonBlocking,
+      move |decision: RetryDecision| {
+        let _ = sender.send(decision);
+        Ok(())
+      },
+    );
+    if status != Status::Ok {
+      return None;
+    }
+
+    receiver.recv_timeout(self.decision_timeout).ok()
+  }
+}
+
+impl Debug for JsRetryPolicy {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("JsRetryPolicy").finish_non_exhaustive()
+  }
+}
+
+impl scylla::retry_policy::RetryPolicy for JsRetryPolicy {
+  fn new_session(&self) -> Box<dyn RetrySession> {
+    Box::new(JsRetrySession {
+      policy: self.clone(),
+      attempt: 0,
+    })
+  }
+
+  fn clone_boxed(&self) -> Box<dyn scylla::retry_policy::RetryPolicy> {
+    Box::new(self.clone())
+  }
+}
+
+struct JsRetrySession {
+  policy: JsRetryPolicy,
+  attempt: u32,
+}
+
+impl RetrySession for JsRetrySession {
+  fn decide_should_retry(&mut self, query_info: QueryInfo) -> scylla::retry_policy::RetryDecision {
+    self.attempt += 1;
+    let info = RetryInfo {
+      error_name: format!(
+        "{:?}",
+        ScyllaError::from_query_error(query_info.error, "").kind()
+      ),
+      message: query_info.error.to_string(),
+      attempt: self.attempt,
+      is_idempotent: query_info.is_idempotent,
+      consistency: query_info.consistency.into(),
+    };
+
+    // Lets the runtime move the other tasks of this thread elsewhere while waiting for the main thread
+    tokio::task::block_in_place(|| self.policy.decide(info))
+      .map_or(scylla::retry_policy::RetryDecision::DontRetry, Into::into)
+  }
+
+  fn reset(&mut self) {
+    self.attempt = 0;
+  }
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use scylla::load_balancing::{DefaultPolicy, LatencyAwarenessBuilder, LoadBalancingPolicy};
+
+/// Settings of the default load balancing policy, which decides the nodes a request is sent to and in which
+/// order they are tried.
+#[napi(object)]
+#[derive(Clone)]
+pub struct LoadBalancingConfig {
+  /// Datacenter whose nodes are tried before the ones of other datacenters, usually the local one
+  pub preferred_datacenter: Option<String>,
+  /// Rack of the preferred datacenter whose nodes are tried first, ignored without `preferredDatacenter`
+  pub preferred_rack: Option<String>,
+  /// Sends requests to the replicas of the data they target first, defaults to `true`
+  pub token_aware: Option<bool>,
+  /// Allows sending requests to nodes outside of the preferred datacenter when the consistency permits it,
+  /// defaults to `false`
+  pub permit_dc_failover: Option<bool>,
+  /// Spreads the load over the replicas by trying them in a random order, defaults to `true`.\
+  /// Disabling it makes a better use of the caches of the database, at the cost of an uneven load.
+  pub enable_shuffling_replicas: Option<bool>,
+  /// Moves the nodes that answer much slower than the others to the end of the query plans.\
+  /// Only enable it when benchmarks prove it beneficial to the workload.
+  pub latency_awareness: Option<LatencyAwarenessConfig>,
+}
+
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct LatencyAwarenessConfig {
+  /// How many times slower than the fastest node a node must be to be penalised, defaults to 2
+  pub exclusion_threshold: Option<f64>,
+  /// How long a node stays penalised, in milliseconds, defaults to 10 seconds
+  pub retry_period: Option<u32>,
+  /// How often the fastest average latency is computed again, in milliseconds, defaults to 100
+  pub update_rate: Option<u32>,
+  /// Number of measurements needed before a node can be penalised, defaults to 50
+  pub minimum_measurements: Option<u32>,
+  /// Weight given to older latencies in the average, in milliseconds, defaults to 100
+  pub scale: Option<u32>,
+}
+
+impl LoadBalancingConfig {
+  pub(crate) fn build_policy(&self) -> Arc<dyn LoadBalancingPolicy> {
+    let mut builder = DefaultPolicy::builder();
+
+    match (&self.preferred_datacenter, &self.preferred_rack) {
+      (Some(datacenter), Some(rack)) => {
+        builder = builder.prefer_datacenter_and_rack(datacenter.clone(), rack.clone());
+      }
+      (Some(datacenter), None) => builder = builder.prefer_datacenter(datacenter.clone()),
+      (None, _) => {}
+    }
+    if let Some(token_aware) = self.token_aware {
+      builder = builder.token_aware(token_aware);
+    }
+    if let Some(permit_dc_failover) = self.permit_dc_failover {
+      builder = builder.permit_dc_failover(permit_dc_failover);
+    }
+    if let Some(enable_shuffling_replicas) = self.enable_shuffling_replicas {
+      builder = builder.enable_shuffling_replicas(enable_shuffling_replicas);
+    }
+    if let Some(latency_awareness) = self.latency_awareness {
+      builder = builder.latency_awareness(latency_awareness.into_builder());
+    }
+
+    builder.build()
+  }
+}
+
+impl LatencyAwarenessConfig {
+  fn into_builder(self) -> LatencyAwarenessBuilder {
+    let millis = |millis: u32| Duration::from_millis(millis.into());
+    let mut builder = LatencyAwarenessBuilder::new();
+
+    if let Some(exclusion_threshold) = self.exclusion_threshold {
+      builder = builder.exclusion_threshold(exclusion_threshold);
+    }
+    if let Some(retry_period) = self.retry_period {
+      builder = builder.retry_period(millis(retry_period));
+    }
+    if let Some(update_rate) = self.update_rate {
+      builder = builder.update_rate(millis(update_rate));
+    }
+    if let Some(minimum_measurements) = self.minimum_measurements {
+      builder = builder.minimum_measurements(minimum_measurements as usize);
+    }
+    if let Some(scale) = self.scale {
+      builder = builder.scale(millis(scale));
+    }
+
+    builder
+  }
+}
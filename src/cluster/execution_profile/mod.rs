@@ -1,19 +1,36 @@
 pub mod consistency;
+pub mod custom_retry_policy;
+pub mod load_balancing;
+pub mod retry_policy;
 pub mod serial_consistency;
+pub mod speculative_execution;
+
+use napi::bindgen_prelude::{FromNapiRef, FromNapiValue};
+use napi::sys;
 
 use self::consistency::Consistency;
+use self::load_balancing::LoadBalancingConfig;
+use self::retry_policy::RetryPolicySetting;
 use self::serial_consistency::SerialConsistency;
+use self::speculative_execution::SpeculativeExecutionConfig;
 
 #[napi(object)]
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct ExecutionProfile {
   pub consistency: Option<Consistency>,
   pub serial_consistency: Option<SerialConsistency>,
+  /// Request timeout in seconds, unlike the timeouts of `Query`, `PreparedStatement` and `QueryOptions` which are in milliseconds. Defaults to 30 seconds
   pub request_timeout: Option<u32>,
+  /// Defaults to a token aware policy without any preferred datacenter
+  pub load_balancing: Option<LoadBalancingConfig>,
+  /// Defaults to `RetryPolicy.Default`
+  pub retry_policy: Option<RetryPolicySetting>,
+  /// Disabled by default
+  pub speculative_execution: Option<SpeculativeExecutionConfig>,
 }
 
 impl ExecutionProfile {
-  fn create_execution_profile(self) -> scylla::ExecutionProfile {
+  fn create_execution_profile(self) -> napi::Result<scylla::ExecutionProfile> {
     let mut ec_builder = scylla::transport::ExecutionProfile::builder();
 
     if let Some(consistency) = self.consistency {
@@ -27,19 +44,79 @@ impl ExecutionProfile {
         ec_builder.request_timeout(Some(std::time::Duration::from_secs(request_timeout.into())));
     }
 
-    ec_builder.build()
+    if let Some(load_balancing) = self.load_balancing {
+      ec_builder = ec_builder.load_balancing_policy(load_balancing.build_policy());
+    }
+
+    if let Some(retry_policy) = self.retry_policy {
+      ec_builder = ec_builder.retry_policy(retry_policy.into_boxed_policy());
+    }
+
+    if let Some(speculative_execution) = self.speculative_execution {
+      ec_builder =
+        ec_builder.speculative_execution_policy(Some(speculative_execution.build_policy()?));
+    }
+
+    Ok(ec_builder.build())
   }
 
-  pub(crate) fn into_handle(self) -> scylla::execution_profile::ExecutionProfileHandle {
-    self.create_execution_profile().into_handle()
+  pub(crate) fn into_handle(
+    self,
+  ) -> napi::Result<scylla::execution_profile::ExecutionProfileHandle> {
+    Ok(self.create_execution_profile()?.into_handle())
   }
 
   pub(crate) fn into_handle_with_label(
     self,
     label: String,
-  ) -> scylla::execution_profile::ExecutionProfileHandle {
-    self
-      .create_execution_profile()
-      .into_handle_with_label(label)
+  ) -> napi::Result<scylla::execution_profile::ExecutionProfileHandle> {
+    Ok(
+      self
+        .create_execution_profile()?
+        .into_handle_with_label(label),
+    )
+  }
+}
+
+/// Execution profile built once, to be shared by statements and requests.
+///
+/// Building a profile creates its load balancing policy, and with `latencyAwareness` a task measuring the
+/// latency of the nodes. Reusing a handle keeps those measurements, which would start from scratch with
+/// every new profile.
+///
+/// # Example
+/// ```javascript
+/// const profile = new ExecutionProfileHandle({ consistency: Consistency.LocalQuorum });
+///
+/// await session.execute(query, params, { executionProfile: profile });
+/// ```
+#[napi]
+#[derive(Clone)]
+pub struct ExecutionProfileHandle {
+  profile: ExecutionProfile,
+  pub(crate) handle: scylla::execution_profile::ExecutionProfileHandle,
+}
+
+#[napi]
+impl ExecutionProfileHandle {
+  #[napi(constructor)]
+  pub fn new(profile: ExecutionProfile) -> napi::Result<Self> {
+    Ok(Self {
+      handle: profile.clone().into_handle()?,
+      profile,
+    })
+  }
+
+  /// Settings the handle was built from
+  #[napi(getter)]
+  pub fn profile(&self) -> ExecutionProfile {
+    self.profile.clone()
+  }
+}
+
+// Lets the handle be a field of option objects, the instance is shared rather than built again
+impl FromNapiValue for ExecutionProfileHandle {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+    unsafe { Self::from_napi_ref(env, napi_val) }.cloned()
   }
 }
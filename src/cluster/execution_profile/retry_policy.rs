@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use napi::bindgen_prelude::{FromNapiRef, FromNapiValue, ToNapiValue};
+use napi::{check_status, sys};
+use scylla::retry_policy::{DefaultRetryPolicy, FallthroughRetryPolicy};
+use scylla::transport::downgrading_consistency_retry_policy::DowngradingConsistencyRetryPolicy;
+
+use super::custom_retry_policy::CustomRetryPolicy;
+
+/// Policy deciding whether a failed request is retried, and on which node.
+#[napi]
+pub enum RetryPolicy {
+  /// Retries when there is a high chance of success, and only idempotent requests when it could lead to
+  /// writing the same data twice
+  Default,
+  /// Never retries, errors are returned as soon as they happen
+  Fallthrough,
+  /// Like `Default`, but also retries at a lower consistency level when not enough replicas answered.\
+  /// Use it only when reading or writing at a lower consistency than requested is acceptable.
+  DowngradingConsistency,
+}
+
+impl RetryPolicy {
+  fn into_boxed_policy(self) -> Box<dyn scylla::retry_policy::RetryPolicy> {
+    match self {
+      RetryPolicy::Default => Box::new(DefaultRetryPolicy::new()),
+      RetryPolicy::Fallthrough => Box::new(FallthroughRetryPolicy::new()),
+      RetryPolicy::DowngradingConsistency => Box::new(DowngradingConsistencyRetryPolicy::new()),
+    }
+  }
+}
+
+/// Either one of the policies of the driver, or a policy deciding from a JS callback.
+///
+/// Declared as `RetryPolicy | CustomRetryPolicy` in `scripts/fix-files.mjs`.
+#[derive(Clone)]
+pub enum RetryPolicySetting {
+  Builtin(RetryPolicy),
+  Custom(CustomRetryPolicy),
+}
+
+impl RetryPolicySetting {
+  pub(crate) fn into_boxed_policy(self) -> Box<dyn scylla::retry_policy::RetryPolicy> {
+    match self {
+      RetryPolicySetting::Builtin(policy) => policy.into_boxed_policy(),
+      RetryPolicySetting::Custom(policy) => Box::new(policy.policy),
+    }
+  }
+
+  pub(crate) fn into_policy(self) -> Arc<dyn scylla::retry_policy::RetryPolicy> {
+    self.into_boxed_policy().into()
+  }
+}
+
+impl FromNapiValue for RetryPolicySetting {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+    let mut value_type = 0;
+    check_status!(
+      unsafe { sys::napi_typeof(env, napi_val, &mut value_type) },
+      "Failed to get the type of the retry policy"
+    )?;
+
+    if value_type == sys::ValueType::napi_number {
+      return unsafe { RetryPolicy::from_napi_value(env, napi_val) }.map(Self::Builtin);
+    }
+
+    unsafe { CustomRetryPolicy::from_napi_ref(env, napi_val) }
+      .map(|policy| Self::Custom(policy.clone()))
+      .map_err(|_| {
+        napi::Error::new(
+          napi::Status::InvalidArg,
+          "Expected a RetryPolicy or a CustomRetryPolicy",
+        )
+      })
+  }
+}
+
+impl ToNapiValue for RetryPolicySetting {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> napi::Result<sys::napi_value> {
+    match val {
+      RetryPolicySetting::Builtin(policy) => unsafe { RetryPolicy::to_napi_value(env, policy) },
+      // A new JS object, sharing the callback of the one the policy was created from
+      RetryPolicySetting::Custom(policy) => unsafe {
+        CustomRetryPolicy::to_napi_value(env, policy)
+      },
+    }
+  }
+}
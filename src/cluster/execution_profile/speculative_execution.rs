@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use scylla::speculative_execution::{
+  PercentileSpeculativeExecutionPolicy, SimpleSpeculativeExecutionPolicy,
+  SpeculativeExecutionPolicy,
+};
+
+/// Default delay before sending a speculative execution, in milliseconds
+const DEFAULT_RETRY_INTERVAL: u32 = 100;
+
+/// Settings of speculative execution, which sends a request to other nodes as well when the first one is slow
+/// to answer, and keeps the first answer received.
+///
+/// Only idempotent statements are executed speculatively, see `setIsIdempotent`.
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct SpeculativeExecutionConfig {
+  /// Maximum number of requests sent in addition to the first one
+  pub max_retry_count: u32,
+  /// Delay between each request, in milliseconds, defaults to 100.\
+  /// Ignored when `percentile` is set.
+  pub retry_interval: Option<u32>,
+  /// Sends another request once the current one is slower than this percentile of the latencies measured
+  /// by the session, e.g. `99.0`, instead of waiting for a fixed delay.\
+  /// Must be greater than 0 and at most 100.
+  pub percentile: Option<f64>,
+}
+
+impl SpeculativeExecutionConfig {
+  pub(crate) fn build_policy(self) -> napi::Result<Arc<dyn SpeculativeExecutionPolicy>> {
+    let max_retry_count = self.max_retry_count as usize;
+
+    Ok(match self.percentile {
+      // Also rejects NaN, which is not part of any range
+      Some(percentile) if !(percentile > 0.0 && percentile <= 100.0) => {
+        return Err(napi::Error::new(
+          napi::Status::InvalidArg,
+          format!(
+            "The percentile of speculative execution must be greater than 0 and at most 100, received {percentile}"
+          ),
+        ));
+      }
+      Some(percentile) => Arc::new(PercentileSpeculativeExecutionPolicy {
+        max_retry_count,
+        percentile,
+      }),
+      None => Arc::new(SimpleSpeculativeExecutionPolicy {
+        max_retry_count,
+        retry_interval: Duration::from_millis(
+          self.retry_interval.unwrap_or(DEFAULT_RETRY_INTERVAL).into(),
+        ),
+      }),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::SpeculativeExecutionConfig;
+
+  fn with_percentile(percentile: f64) -> SpeculativeExecutionConfig {
+    SpeculativeExecutionConfig {
+      max_retry_count: 2,
+      retry_interval: None,
+      percentile: Some(percentile),
+    }
+  }
+
+  #[test]
+  fn percentile_must_be_in_range() {
+    assert!(with_percentile(99.0).build_policy().is_ok());
+    assert!(with_percentile(100.0).build_policy().is_ok());
+
+    for percentile in [0.0, -1.0, 100.5, f64::NAN, f64::INFINITY] {
+      assert!(with_percentile(percentile).build_policy().is_err());
+    }
+  }
+}
@@ -1,3 +1,4 @@
+use std::net::SocketAddr;
 use std::time::Duration;
 
 use napi::Either;
@@ -8,16 +9,18 @@ use crate::{
     cluster_config::{compression::Compression, ClusterConfig},
     execution_profile::ExecutionProfile,
   },
-  session::scylla_session::ScyllaSession,
+  error::ScyllaError,
+  session::{prepared_cache::DEFAULT_PREPARED_CACHE_SIZE, scylla_session::ScyllaSession},
 };
 
 #[napi(js_name = "Cluster")]
 struct ScyllaCluster {
-  uri: String,
+  nodes: Vec<String>,
   compression: Option<Compression>,
   default_execution_profile: Option<ExecutionProfile>,
   auto_await_schema_agreement: Option<bool>,
   schema_agreement_interval: Option<Duration>,
+  prepared_statement_cache_size: Option<u32>,
 
   // connection fields
   connection: Option<ConnectionOptions>,
@@ -60,8 +63,11 @@ impl ScyllaCluster {
   /// {
   ///     nodes: Array<string>,
   /// }
+  ///
+  /// Every node is used as a contact point, so the session can be established as long as one of them is reachable.
+  /// Nodes can be given as `ip:port`, `hostname:port` or just `hostname`, in which case the default port 9042 is used.
   #[napi(constructor)]
-  pub fn new(cluster_config: ClusterConfig) -> Self {
+  pub fn new(cluster_config: ClusterConfig) -> napi::Result<Self> {
     let ClusterConfig {
       nodes,
       compression,
@@ -71,12 +77,18 @@ impl ScyllaCluster {
       ssl,
       auto_await_schema_agreement,
       schema_agreement_interval,
+      prepared_statement_cache_size,
     } = cluster_config;
 
-    let uri = nodes.first().expect("at least one node is required");
+    if nodes.is_empty() {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "At least one node is required",
+      ));
+    }
 
-    Self {
-      uri: uri.to_string(),
+    Ok(Self {
+      nodes,
       compression,
       default_execution_profile,
       connection: Some(ConnectionOptions {
@@ -86,7 +98,8 @@ impl ScyllaCluster {
       }),
       auto_await_schema_agreement,
       schema_agreement_interval: schema_agreement_interval.map(|d| Duration::from_secs(d as u64)),
-    }
+      prepared_statement_cache_size,
+    })
   }
 
   #[napi]
@@ -96,7 +109,7 @@ impl ScyllaCluster {
     keyspace_or_options: Option<Either<String, ConnectionOptions>>,
     options: Option<ConnectionOptions>,
   ) -> napi::Result<ScyllaSession> {
-    let mut builder = scylla::SessionBuilder::new().known_node(self.uri.as_str());
+    let mut builder = scylla::SessionBuilder::new().known_nodes(&self.nodes);
 
     // TODO: We need to think of a better way to deal with keyspace possibly being options
     let keyspace: Result<Option<String>, napi::Error> = match (&keyspace_or_options, &options) {
@@ -259,7 +272,8 @@ impl ScyllaCluster {
     }
 
     if let Some(default_execution_profile) = &self.default_execution_profile {
-      builder = builder.default_execution_profile_handle(default_execution_profile.into_handle());
+      builder =
+        builder.default_execution_profile_handle(default_execution_profile.clone().into_handle()?);
     }
 
     if let Some(compression) = self.compression {
@@ -269,15 +283,64 @@ impl ScyllaCluster {
     let session = builder.build().await;
 
     match session {
-      Ok(session) => Ok(ScyllaSession::new(session)),
-      Err(err) => Err(napi::Error::from_reason(format!(
-        "Failed to connect to the database: {} - [{uri}] - Keyspace: {keyspace}",
-        err,
-        uri = self.uri,
-        keyspace = keyspace
-          .unwrap_or(Some("No keyspace provided".to_string()))
-          .unwrap_or("No keyspace provided".to_string())
-      ))),
+      Ok(session) => Ok(ScyllaSession::new(
+        session,
+        self
+          .prepared_statement_cache_size
+          .unwrap_or(DEFAULT_PREPARED_CACHE_SIZE),
+      )),
+      Err(err) => {
+        let report =
+          futures::future::join_all(self.nodes.iter().map(|node| probe_node(node))).await;
+
+        Err(
+          ScyllaError::from_new_session_error(
+            &err,
+            format!(
+              "Failed to connect to the database - Keyspace: {keyspace}\nTCP probe of the contact points, TLS and authentication are not checked:\n{report}",
+              keyspace = keyspace
+                .unwrap_or(Some("No keyspace provided".to_string()))
+                .unwrap_or("No keyspace provided".to_string()),
+              report = report.join("\n"),
+            ),
+          )
+          .into(),
+        )
+      }
     }
   }
 }
+
+/// How long the probe waits for the TCP connection to each address of a contact point
+const PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Resolves a contact point and opens a plain TCP connection to each of its addresses, to explain why a
+/// connection attempt failed.\
+/// Only the TCP port is probed: an open port does not mean TLS or authentication would succeed.
+async fn probe_node(node: &str) -> String {
+  // Same resolution as the driver: the port is optional and defaults to 9042
+  let addresses = match tokio::net::lookup_host(node).await {
+    Ok(addresses) => Ok(addresses.collect::<Vec<_>>()),
+    Err(err) => tokio::net::lookup_host((node, 9042))
+      .await
+      .map(|addresses| addresses.collect())
+      .map_err(|_| err),
+  };
+
+  let addresses = match addresses {
+    Ok(addresses) if !addresses.is_empty() => addresses,
+    Ok(_) => return format!("  - [{node}] could not be resolved: no addresses found"),
+    Err(err) => return format!("  - [{node}] could not be resolved: {err}"),
+  };
+
+  let results = futures::future::join_all(addresses.into_iter().map(probe_address)).await;
+  format!("  - [{node}] {}", results.join(", "))
+}
+
+async fn probe_address(address: SocketAddr) -> String {
+  match tokio::time::timeout(PROBE_TIMEOUT, tokio::net::TcpStream::connect(address)).await {
+    Ok(Ok(_)) => format!("{address}: TCP port open"),
+    Ok(Err(err)) => format!("{address}: TCP connection failed ({err})"),
+    Err(_) => format!("{address}: TCP connection timed out after {PROBE_TIMEOUT:?}"),
+  }
+}
@@ -1,3 +1,10 @@
+use std::fmt::Display;
+
+use scylla::transport::errors::{BadQuery, DbError, NewSessionError, QueryError, WriteType};
+use serde::Serialize;
+
+use crate::cluster::execution_profile::consistency::Consistency;
+
 // https://github.com/surrealdb/surrealdb.node/blob/main/src/error.rs
 pub fn pipe_error(err: impl std::error::Error) -> napi::Error {
   napi::Error::from_reason(err.to_string())
@@ -6,3 +13,353 @@ pub fn pipe_error(err: impl std::error::Error) -> napi::Error {
 pub fn pipe_error_from_string(err: impl std::string::ToString) -> napi::Error {
   napi::Error::from_reason(err.to_string())
 }
+
+/// Wraps a failed query into a typed error, prefixing its message with `context`
+pub fn query_error(err: QueryError, context: impl Display) -> napi::Error {
+  ScyllaError::from_query_error(&err, context).into()
+}
+
+/// Name of the JS error class thrown for a failure.
+///
+/// The classes themselves are declared in `scripts/fix-files.mjs`, which rebuilds them from the serialized
+/// [`ScyllaError`] carried by the message of the native error.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+  ScyllaError,
+  ReadTimeoutError,
+  WriteTimeoutError,
+  ReadFailureError,
+  WriteFailureError,
+  UnavailableError,
+  OverloadedError,
+  /// Named so that it does not shadow the global `SyntaxError` of JS
+  CqlSyntaxError,
+  InvalidQueryError,
+  AlreadyExistsError,
+  AuthenticationError,
+  UnauthorizedError,
+  ConnectionError,
+  RequestTimeoutError,
+  SerializationError,
+}
+
+/// Structured fields attached to the JS error, only the ones relevant to the error kind are set
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorDetails {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub consistency: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub received: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub required: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub alive: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub num_failures: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub data_present: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub write_type: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub keyspace: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub table: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ScyllaError {
+  name: ErrorKind,
+  message: String,
+  #[serde(flatten)]
+  details: ErrorDetails,
+}
+
+impl ScyllaError {
+  pub fn new(kind: ErrorKind, message: impl ToString) -> Self {
+    Self {
+      name: kind,
+      message: message.to_string(),
+      details: Default::default(),
+    }
+  }
+
+  pub fn with_details(mut self, details: ErrorDetails) -> Self {
+    self.details = details;
+    self
+  }
+
+  pub fn kind(&self) -> ErrorKind {
+    self.name
+  }
+
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  pub fn from_query_error(err: &QueryError, context: impl Display) -> Self {
+    let message = format!("{context}\n{err}");
+
+    match err {
+      QueryError::DbError(db_error, _) => Self::from_db_error(db_error, message),
+      QueryError::BadQuery(BadQuery::SerializeValuesError(_))
+      | QueryError::BadQuery(BadQuery::SerializationError(_))
+      | QueryError::BadQuery(BadQuery::ValuesTooLongForKey(..)) => {
+        Self::new(ErrorKind::SerializationError, message)
+      }
+      QueryError::BadQuery(_) => Self::new(ErrorKind::InvalidQueryError, message),
+      QueryError::IoError(_)
+      | QueryError::TooManyOrphanedStreamIds(_)
+      | QueryError::UnableToAllocStreamId
+      | QueryError::TranslationError(_) => Self::new(ErrorKind::ConnectionError, message),
+      QueryError::TimeoutError | QueryError::RequestTimeout(_) => {
+        Self::new(ErrorKind::RequestTimeoutError, message)
+      }
+      QueryError::ProtocolError(_) | QueryError::InvalidMessage(_) => {
+        Self::new(ErrorKind::ScyllaError, message)
+      }
+    }
+  }
+
+  pub fn from_new_session_error(err: &NewSessionError, context: impl Display) -> Self {
+    let message = format!("{context}\n{err}");
+
+    match err {
+      NewSessionError::DbError(db_error, _) => Self::from_db_error(db_error, message),
+      NewSessionError::BadQuery(_) => Self::new(ErrorKind::InvalidQueryError, message),
+      NewSessionError::RequestTimeout(_) => Self::new(ErrorKind::RequestTimeoutError, message),
+      _ => Self::new(ErrorKind::ConnectionError, message),
+    }
+  }
+
+  fn from_db_error(err: &DbError, message: String) -> Self {
+    let consistency =
+      |consistency: &scylla::statement::Consistency| Some(Consistency::from(*consistency) as i32);
+
+    match err {
+      DbError::ReadTimeout {
+        consistency: c,
+        received,
+        required,
+        data_present,
+      } => Self::new(ErrorKind::ReadTimeoutError, message).with_details(ErrorDetails {
+        consistency: consistency(c),
+        received: Some(*received),
+        required: Some(*required),
+        data_present: Some(*data_present),
+        ..Default::default()
+      }),
+      DbError::WriteTimeout {
+        consistency: c,
+        received,
+        required,
+        write_type,
+      } => Self::new(ErrorKind::WriteTimeoutError, message).with_details(ErrorDetails {
+        consistency: consistency(c),
+        received: Some(*received),
+        required: Some(*required),
+        write_type: Some(write_type_name(write_type)),
+        ..Default::default()
+      }),
+      DbError::ReadFailure {
+        consistency: c,
+        received,
+        required,
+        numfailures,
+        data_present,
+      } => Self::new(ErrorKind::ReadFailureError, message).with_details(ErrorDetails {
+        consistency: consistency(c),
+        received: Some(*received),
+        required: Some(*required),
+        num_failures: Some(*numfailures),
+        data_present: Some(*data_present),
+        ..Default::default()
+      }),
+      DbError::WriteFailure {
+        consistency: c,
+        received,
+        required,
+        numfailures,
+        write_type,
+      } => Self::new(ErrorKind::WriteFailureError, message).with_details(ErrorDetails {
+        consistency: consistency(c),
+        received: Some(*received),
+        required: Some(*required),
+        num_failures: Some(*numfailures),
+        write_type: Some(write_type_name(write_type)),
+        ..Default::default()
+      }),
+      DbError::Unavailable {
+        consistency: c,
+        required,
+        alive,
+      } => Self::new(ErrorKind::UnavailableError, message).with_details(ErrorDetails {
+        consistency: consistency(c),
+        required: Some(*required),
+        alive: Some(*alive),
+        ..Default::default()
+      }),
+      DbError::AlreadyExists { keyspace, table } => {
+        Self::new(ErrorKind::AlreadyExistsError, message).with_details(ErrorDetails {
+          keyspace: Some(keyspace.clone()),
+          // In case of a keyspace creation the table is an empty string
+          table: Some(table.clone()).filter(|table| !table.is_empty()),
+          ..Default::default()
+        })
+      }
+      DbError::FunctionFailure { keyspace, .. } => Self::new(ErrorKind::ScyllaError, message)
+        .with_details(ErrorDetails {
+          keyspace: Some(keyspace.clone()),
+          ..Default::default()
+        }),
+      DbError::SyntaxError => Self::new(ErrorKind::CqlSyntaxError, message),
+      DbError::Invalid | DbError::ConfigError => Self::new(ErrorKind::InvalidQueryError, message),
+      DbError::AuthenticationError => Self::new(ErrorKind::AuthenticationError, message),
+      DbError::Unauthorized => Self::new(ErrorKind::UnauthorizedError, message),
+      DbError::Overloaded | DbError::RateLimitReached { .. } => {
+        Self::new(ErrorKind::OverloadedError, message)
+      }
+      _ => Self::new(ErrorKind::ScyllaError, message),
+    }
+  }
+}
+
+fn write_type_name(write_type: &WriteType) -> String {
+  match write_type {
+    WriteType::Other(name) => name.clone(),
+    write_type => write_type.to_string(),
+  }
+}
+
+impl From<ScyllaError> for napi::Error {
+  fn from(err: ScyllaError) -> Self {
+    let reason = serde_json::to_string(&err).unwrap_or(err.message);
+    napi::Error::new(napi::Status::GenericFailure, reason)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+
+  fn db_error(err: DbError) -> ScyllaError {
+    ScyllaError::from_query_error(&QueryError::DbError(err, "reason".to_string()), "context")
+  }
+
+  #[test]
+  fn db_errors_carry_their_details() {
+    let err = db_error(DbError::ReadTimeout {
+      consistency: scylla::statement::Consistency::Quorum,
+      received: 1,
+      required: 2,
+      data_present: false,
+    });
+
+    assert_eq!(err.kind(), ErrorKind::ReadTimeoutError);
+    assert!(err.message().starts_with("context\n"));
+    assert_eq!(
+      err.details,
+      ErrorDetails {
+        consistency: Some(Consistency::Quorum as i32),
+        received: Some(1),
+        required: Some(2),
+        data_present: Some(false),
+        ..Default::default()
+      }
+    );
+
+    let err = db_error(DbError::WriteTimeout {
+      consistency: scylla::statement::Consistency::One,
+      received: 0,
+      required: 1,
+      write_type: WriteType::Other("CUSTOM".to_string()),
+    });
+    assert_eq!(err.kind(), ErrorKind::WriteTimeoutError);
+    assert_eq!(err.details.write_type.as_deref(), Some("CUSTOM"));
+
+    let err = db_error(DbError::Unavailable {
+      consistency: scylla::statement::Consistency::All,
+      required: 3,
+      alive: 1,
+    });
+    assert_eq!(err.kind(), ErrorKind::UnavailableError);
+    assert_eq!(
+      (err.details.required, err.details.alive),
+      (Some(3), Some(1))
+    );
+  }
+
+  #[test]
+  fn already_exists_omits_the_table_of_a_keyspace() {
+    let err = db_error(DbError::AlreadyExists {
+      keyspace: "ks".to_string(),
+      table: String::new(),
+    });
+
+    assert_eq!(err.kind(), ErrorKind::AlreadyExistsError);
+    assert_eq!(err.details.keyspace.as_deref(), Some("ks"));
+    assert_eq!(err.details.table, None);
+  }
+
+  #[test]
+  fn db_errors_map_to_their_kind() {
+    for (err, kind) in [
+      (DbError::SyntaxError, ErrorKind::CqlSyntaxError),
+      (DbError::Invalid, ErrorKind::InvalidQueryError),
+      (DbError::ConfigError, ErrorKind::InvalidQueryError),
+      (DbError::AuthenticationError, ErrorKind::AuthenticationError),
+      (DbError::Unauthorized, ErrorKind::UnauthorizedError),
+      (DbError::Overloaded, ErrorKind::OverloadedError),
+      (DbError::ServerError, ErrorKind::ScyllaError),
+    ] {
+      assert_eq!(db_error(err).kind(), kind);
+    }
+  }
+
+  #[test]
+  fn query_errors_map_to_their_kind() {
+    for (err, kind) in [
+      (
+        QueryError::BadQuery(BadQuery::ValuesTooLongForKey(1, 0)),
+        ErrorKind::SerializationError,
+      ),
+      (
+        QueryError::BadQuery(BadQuery::Other("bad".to_string())),
+        ErrorKind::InvalidQueryError,
+      ),
+      (
+        QueryError::IoError(Arc::new(std::io::Error::from(
+          std::io::ErrorKind::ConnectionReset,
+        ))),
+        ErrorKind::ConnectionError,
+      ),
+      (QueryError::TimeoutError, ErrorKind::RequestTimeoutError),
+      (
+        QueryError::RequestTimeout("late".to_string()),
+        ErrorKind::RequestTimeoutError,
+      ),
+      (QueryError::ProtocolError("broken"), ErrorKind::ScyllaError),
+    ] {
+      assert_eq!(ScyllaError::from_query_error(&err, "").kind(), kind);
+    }
+  }
+
+  #[test]
+  fn errors_are_serialized_for_js() {
+    let err: napi::Error = db_error(DbError::AlreadyExists {
+      keyspace: "ks".to_string(),
+      table: "tab".to_string(),
+    })
+    .into();
+
+    let reason: serde_json::Value = serde_json::from_str(&err.reason).unwrap();
+
+    assert_eq!(reason["name"], "AlreadyExistsError");
+    assert!(reason["message"].as_str().unwrap().starts_with("context\n"));
+    assert_eq!(reason["keyspace"], "ks");
+    assert_eq!(reason["table"], "tab");
+    assert!(reason.get("consistency").is_none());
+  }
+}
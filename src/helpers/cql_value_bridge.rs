@@ -1,24 +1,33 @@
-use napi::bindgen_prelude::{BigInt, Either14, Either15};
+use chrono::{DateTime, Utc};
+use napi::bindgen_prelude::{BigInt, Either18, Either19};
+use napi::Either;
 use scylla::frame::response::result::CqlValue;
 
 use std::collections::HashMap;
 
 use crate::types::{
-  decimal::Decimal, double::Double, duration::Duration, float::Float, list::List, map::Map,
-  set::Set, uuid::Uuid, varint::Varint,
+  blob::Blob, decimal::Decimal, double::Double, duration::Duration, float::Float, list::List,
+  local_date::LocalDate, local_time::LocalTime, map::Map, set::Set, tuple::Tuple, unset::Unset,
+  uuid::Uuid, varint::Varint,
 };
 
 use super::to_cql_value::ToCqlValue;
 
 macro_rules! define_expected_type {
     ($lifetime:lifetime, $($t:ty),+) => {
-      pub type ParameterNativeTypes<$lifetime> = Either14<$($t),+>;
-      pub type ParameterWithMapType<$lifetime> = Either15<$($t),+, HashMap<String, ParameterNativeTypes<$lifetime>>>;
-      pub type JSQueryParameters<$lifetime> = napi::Result<Vec<HashMap<String, ParameterWithMapType<$lifetime>>>>;
+      pub type ParameterNativeTypes<$lifetime> = Either18<$($t),+>;
+      pub type ParameterWithMapType<$lifetime> = Either19<$($t),+, HashMap<String, ParameterNativeTypes<$lifetime>>>;
     };
 }
 
-define_expected_type!('a, u32, String, &'a Uuid, BigInt, &'a Duration, &'a Decimal, bool, Vec<u32>, &'a Float, &'a Varint, &'a List, &'a Set, &'a Map, &'a Double);
+define_expected_type!('a, f64, String, &'a Uuid, BigInt, &'a Duration, &'a Decimal, bool, Blob, &'a Float, &'a Varint, &'a List, &'a Set, &'a Map, &'a Double, DateTime<Utc>, &'a LocalDate, &'a LocalTime, &'a Tuple);
+
+/// A bound parameter: `null` and `undefined` are sent as null, `UNSET` leaves the column untouched
+pub type ParameterValue<'a> = Option<Either<&'a Unset, ParameterWithMapType<'a>>>;
+
+/// Parameters of a statement, either positional or bound by name to the markers of the statement (`:name`)
+pub type JSQueryParameters<'a> =
+  Either<Vec<ParameterValue<'a>>, HashMap<String, ParameterValue<'a>>>;
 
 impl<'a> ToCqlValue for ParameterWithMapType<'a> {
   fn to_cql_value(&self) -> CqlValue {
@@ -30,14 +39,18 @@ impl<'a> ToCqlValue for ParameterWithMapType<'a> {
       ParameterWithMapType::E(duration) => duration.to_cql_value(),
       ParameterWithMapType::F(decimal) => decimal.to_cql_value(),
       ParameterWithMapType::G(bool_val) => bool_val.to_cql_value(),
-      ParameterWithMapType::H(buffer) => buffer.to_cql_value(),
+      ParameterWithMapType::H(blob) => blob.to_cql_value(),
       ParameterWithMapType::I(float) => float.to_cql_value(),
       ParameterWithMapType::J(varint) => varint.to_cql_value(),
       ParameterWithMapType::K(list) => list.to_cql_value(),
       ParameterWithMapType::L(set) => set.to_cql_value(),
       ParameterWithMapType::M(map) => map.to_cql_value(),
       ParameterWithMapType::N(double) => double.to_cql_value(),
-      ParameterWithMapType::O(map) => CqlValue::UserDefinedType {
+      ParameterWithMapType::O(timestamp) => timestamp.to_cql_value(),
+      ParameterWithMapType::P(date) => date.to_cql_value(),
+      ParameterWithMapType::Q(time) => time.to_cql_value(),
+      ParameterWithMapType::R(tuple) => tuple.to_cql_value(),
+      ParameterWithMapType::S(map) => CqlValue::UserDefinedType {
         // TODO: think a better way to fill this info here
         keyspace: "keyspace".to_string(),
         type_name: "type_name".to_string(),
@@ -50,6 +63,31 @@ impl<'a> ToCqlValue for ParameterWithMapType<'a> {
   }
 }
 
+/// Name of the JS type a parameter was read from, used in error messages
+pub fn js_type_name(parameter: &ParameterWithMapType<'_>) -> &'static str {
+  match parameter {
+    ParameterWithMapType::A(_) => "number",
+    ParameterWithMapType::B(_) => "string",
+    ParameterWithMapType::C(_) => "Uuid",
+    ParameterWithMapType::D(_) => "bigint",
+    ParameterWithMapType::E(_) => "Duration",
+    ParameterWithMapType::F(_) => "Decimal",
+    ParameterWithMapType::G(_) => "boolean",
+    ParameterWithMapType::H(_) => "Buffer",
+    ParameterWithMapType::I(_) => "Float",
+    ParameterWithMapType::J(_) => "Varint",
+    ParameterWithMapType::K(_) => "List",
+    ParameterWithMapType::L(_) => "Set",
+    ParameterWithMapType::M(_) => "Map",
+    ParameterWithMapType::N(_) => "Double",
+    ParameterWithMapType::O(_) => "Date",
+    ParameterWithMapType::P(_) => "LocalDate",
+    ParameterWithMapType::Q(_) => "LocalTime",
+    ParameterWithMapType::R(_) => "Tuple",
+    ParameterWithMapType::S(_) => "object",
+  }
+}
+
 impl<'a> ToCqlValue for ParameterNativeTypes<'a> {
   fn to_cql_value(&self) -> CqlValue {
     match self {
@@ -60,13 +98,17 @@ impl<'a> ToCqlValue for ParameterNativeTypes<'a> {
       ParameterNativeTypes::E(duration) => duration.to_cql_value(),
       ParameterNativeTypes::F(decimal) => decimal.to_cql_value(),
       ParameterNativeTypes::G(bool_val) => bool_val.to_cql_value(),
-      ParameterNativeTypes::H(buffer) => buffer.to_cql_value(),
+      ParameterNativeTypes::H(blob) => blob.to_cql_value(),
       ParameterNativeTypes::J(varint) => varint.to_cql_value(),
       ParameterNativeTypes::I(float) => float.to_cql_value(),
       ParameterNativeTypes::K(list) => list.to_cql_value(),
       ParameterNativeTypes::L(set) => set.to_cql_value(),
       ParameterNativeTypes::M(map) => map.to_cql_value(),
       ParameterNativeTypes::N(double) => double.to_cql_value(),
+      ParameterNativeTypes::O(timestamp) => timestamp.to_cql_value(),
+      ParameterNativeTypes::P(date) => date.to_cql_value(),
+      ParameterNativeTypes::Q(time) => time.to_cql_value(),
+      ParameterNativeTypes::R(tuple) => tuple.to_cql_value(),
     }
   }
 }
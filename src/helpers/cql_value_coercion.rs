@@ -0,0 +1,617 @@
+use std::fmt::Display;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use num_bigint::BigInt;
+use scylla::frame::response::result::{ColumnType, CqlValue};
+use scylla::frame::value::{
+  Counter, CqlDate, CqlDecimal, CqlTime, CqlTimestamp, CqlTimeuuid, CqlVarint,
+};
+use scylla::serialize::SerializationError;
+
+/// `Number.MAX_SAFE_INTEGER`, larger JS numbers cannot represent every integer
+pub(crate) const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Value that cannot be represented as the type of the column it is bound to
+#[derive(Debug, Clone)]
+pub struct CoercionError {
+  value: String,
+  typ: String,
+  reason: Option<String>,
+}
+
+impl CoercionError {
+  fn new(value: &CqlValue, typ: &ColumnType) -> Self {
+    Self {
+      value: format!("{value:?}"),
+      typ: cql_type_name(typ),
+      reason: None,
+    }
+  }
+
+  fn because(mut self, reason: impl Display) -> Self {
+    self.reason = Some(reason.to_string());
+    self
+  }
+}
+
+impl Display for CoercionError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Cannot convert {} to {}", self.value, self.typ)?;
+    if let Some(reason) = &self.reason {
+      write!(f, ": {reason}")?;
+    }
+    Ok(())
+  }
+}
+
+impl std::error::Error for CoercionError {}
+
+impl From<CoercionError> for SerializationError {
+  fn from(err: CoercionError) -> Self {
+    SerializationError::new(err)
+  }
+}
+
+/// Converts a value built from a JS parameter into the type of the bind marker it is bound to.
+///
+/// JS values map to a single CQL type (a number is an `int`, a string is a `text`...), this widens or parses
+/// them into the type the database expects, so plain JS values can be bound to `bigint`, `double`, `uuid`,
+/// `timestamp`... columns without wrapping them first.\
+/// Values that have no sensible conversion are returned as is and rejected by the type check of the driver.
+pub fn coerce_cql_value(value: CqlValue, typ: &ColumnType) -> Result<CqlValue, CoercionError> {
+  match (value, typ) {
+    (value @ (CqlValue::Int(_) | CqlValue::BigInt(_) | CqlValue::Double(_)), typ) => {
+      coerce_number(value, typ)
+    }
+    (CqlValue::Text(text), typ) => coerce_text(text, typ),
+    (
+      value @ CqlValue::Varint(_),
+      ColumnType::TinyInt
+      | ColumnType::SmallInt
+      | ColumnType::Int
+      | ColumnType::BigInt
+      | ColumnType::Counter
+      | ColumnType::Timestamp,
+    ) => Err(CoercionError::new(&value, typ).because("value does not fit in 64 bits")),
+
+    (CqlValue::List(values) | CqlValue::Set(values), ColumnType::List(element_type)) => {
+      Ok(CqlValue::List(coerce_all(values, element_type)?))
+    }
+    (CqlValue::List(values) | CqlValue::Set(values), ColumnType::Set(element_type)) => {
+      Ok(CqlValue::Set(coerce_all(values, element_type)?))
+    }
+    (CqlValue::Map(entries), ColumnType::Map(key_type, value_type)) => Ok(CqlValue::Map(
+      entries
+        .into_iter()
+        .map(|(key, value)| {
+          Ok((
+            coerce_cql_value(key, key_type)?,
+            coerce_cql_value(value, value_type)?,
+          ))
+        })
+        .collect::<Result<_, CoercionError>>()?,
+    )),
+    (CqlValue::Tuple(values), ColumnType::Tuple(element_types)) => {
+      if values.len() > element_types.len() {
+        return Err(
+          CoercionError::new(&CqlValue::Tuple(values), typ)
+            .because(format!("expected at most {} elements", element_types.len())),
+        );
+      }
+
+      Ok(CqlValue::Tuple(
+        values
+          .into_iter()
+          .zip(element_types)
+          .map(|(value, element_type)| {
+            value
+              .map(|value| coerce_cql_value(value, element_type))
+              .transpose()
+          })
+          .collect::<Result<_, CoercionError>>()?,
+      ))
+    }
+    (
+      CqlValue::UserDefinedType {
+        keyspace: value_keyspace,
+        type_name: value_type_name,
+        mut fields,
+      },
+      ColumnType::UserDefinedType {
+        type_name,
+        keyspace,
+        field_types,
+      },
+    ) => {
+      let unknown = fields
+        .iter()
+        .filter(|(field, _)| !field_types.iter().any(|(name, _)| name == field))
+        .map(|(field, _)| format!("`{field}`"))
+        .collect::<Vec<_>>();
+      if !unknown.is_empty() {
+        let reason = format!("the type has no field named {}", unknown.join(", "));
+        let value = CqlValue::UserDefinedType {
+          keyspace: value_keyspace,
+          type_name: value_type_name,
+          fields,
+        };
+        return Err(CoercionError::new(&value, typ).because(reason));
+      }
+
+      // Fields are sent in the order of the type definition, whatever the order of the keys in the JS object
+      let fields = field_types
+        .iter()
+        .map(|(name, field_type)| {
+          let value = fields
+            .iter()
+            .position(|(field, _)| field == name)
+            .and_then(|index| fields.swap_remove(index).1);
+
+          Ok((
+            name.clone(),
+            value
+              .map(|value| coerce_cql_value(value, field_type))
+              .transpose()?,
+          ))
+        })
+        .collect::<Result<_, CoercionError>>()?;
+
+      Ok(CqlValue::UserDefinedType {
+        keyspace: keyspace.clone(),
+        type_name: type_name.clone(),
+        fields,
+      })
+    }
+
+    (value, _) => Ok(value),
+  }
+}
+
+fn coerce_all(values: Vec<CqlValue>, typ: &ColumnType) -> Result<Vec<CqlValue>, CoercionError> {
+  values
+    .into_iter()
+    .map(|value| coerce_cql_value(value, typ))
+    .collect()
+}
+
+fn coerce_number(value: CqlValue, typ: &ColumnType) -> Result<CqlValue, CoercionError> {
+  let error = || CoercionError::new(&value, typ);
+
+  // Whole numbers are kept as integers to avoid losing precision on 64 bit values
+  let integer = match value {
+    CqlValue::Int(int) => Ok(i64::from(int)),
+    CqlValue::BigInt(int) => Ok(int),
+    CqlValue::Double(double) if double.fract() != 0.0 || !double.is_finite() => {
+      Err("value must be an integer")
+    }
+    CqlValue::Double(double) if double.abs() > MAX_SAFE_INTEGER => {
+      Err("value is not a safe integer, pass it as a BigInt instead")
+    }
+    CqlValue::Double(double) => Ok(double as i64),
+    _ => unreachable!(),
+  };
+  let double = match value {
+    CqlValue::Int(int) => f64::from(int),
+    CqlValue::BigInt(int) => int as f64,
+    CqlValue::Double(double) => double,
+    _ => unreachable!(),
+  };
+
+  let integer_in_range = |min: i64, max: i64| match integer {
+    Ok(integer) if (min..=max).contains(&integer) => Ok(integer),
+    Ok(_) => Err(error().because(format!("value must be between {min} and {max}"))),
+    Err(reason) => Err(error().because(reason)),
+  };
+
+  Ok(match typ {
+    ColumnType::TinyInt => {
+      CqlValue::TinyInt(integer_in_range(i8::MIN.into(), i8::MAX.into())? as i8)
+    }
+    ColumnType::SmallInt => {
+      CqlValue::SmallInt(integer_in_range(i16::MIN.into(), i16::MAX.into())? as i16)
+    }
+    ColumnType::Int => CqlValue::Int(integer_in_range(i32::MIN.into(), i32::MAX.into())? as i32),
+    ColumnType::BigInt => CqlValue::BigInt(integer_in_range(i64::MIN, i64::MAX)?),
+    ColumnType::Counter => CqlValue::Counter(Counter(integer_in_range(i64::MIN, i64::MAX)?)),
+    // Milliseconds since the unix epoch, like `Date.prototype.getTime`
+    ColumnType::Timestamp => {
+      CqlValue::Timestamp(CqlTimestamp(integer_in_range(i64::MIN, i64::MAX)?))
+    }
+    // Nanoseconds since midnight
+    ColumnType::Time => CqlValue::Time(CqlTime(integer_in_range(0, 86_399_999_999_999)?)),
+    ColumnType::Varint => CqlValue::Varint(CqlVarint::from(BigInt::from(integer_in_range(
+      i64::MIN,
+      i64::MAX,
+    )?))),
+    // Infinities and NaN are valid `float` values, finite values must not overflow it
+    ColumnType::Float
+      if double.is_finite() && !(f32::MIN.into()..=f32::MAX.into()).contains(&double) =>
+    {
+      return Err(error().because(format!(
+        "value must be between {} and {}",
+        f32::MIN,
+        f32::MAX
+      )));
+    }
+    ColumnType::Float => CqlValue::Float(double as f32),
+    ColumnType::Double => CqlValue::Double(double),
+    ColumnType::Decimal => CqlValue::Decimal(
+      integer
+        .map(|integer| Ok(BigDecimal::from(integer)))
+        .unwrap_or_else(|_| BigDecimal::try_from(double).map_err(|err| error().because(err)))
+        .and_then(|decimal| to_cql_decimal(decimal).map_err(|err| error().because(err)))?,
+    ),
+    _ => value,
+  })
+}
+
+fn coerce_text(text: String, typ: &ColumnType) -> Result<CqlValue, CoercionError> {
+  let error =
+    |reason: &dyn Display| CoercionError::new(&CqlValue::Text(text.clone()), typ).because(reason);
+
+  Ok(match typ {
+    ColumnType::Ascii if text.is_ascii() => CqlValue::Ascii(text),
+    ColumnType::Ascii => return Err(error(&"value contains non-ASCII characters")),
+    ColumnType::Uuid => CqlValue::Uuid(uuid::Uuid::parse_str(&text).map_err(|err| error(&err))?),
+    ColumnType::Timeuuid => CqlValue::Timeuuid(CqlTimeuuid::from(
+      uuid::Uuid::parse_str(&text).map_err(|err| error(&err))?,
+    )),
+    ColumnType::Inet => CqlValue::Inet(IpAddr::from_str(&text).map_err(|err| error(&err))?),
+    ColumnType::Timestamp => {
+      CqlValue::Timestamp(parse_timestamp(&text).map_err(|err| error(&err))?)
+    }
+    ColumnType::Date => CqlValue::Date(CqlDate::from(
+      NaiveDate::from_str(&text).map_err(|err| error(&err))?,
+    )),
+    ColumnType::Time => CqlValue::Time(
+      NaiveTime::from_str(&text)
+        .map_err(|err| error(&err))
+        .and_then(|time| CqlTime::try_from(time).map_err(|err| error(&err)))?,
+    ),
+    ColumnType::Decimal => CqlValue::Decimal(
+      BigDecimal::from_str(&text)
+        .map_err(|err| error(&err))
+        .and_then(|decimal| to_cql_decimal(decimal).map_err(|err| error(&err)))?,
+    ),
+    ColumnType::Varint => CqlValue::Varint(CqlVarint::from(
+      BigInt::from_str(&text).map_err(|err| error(&err))?,
+    )),
+    ColumnType::BigInt => CqlValue::BigInt(text.parse().map_err(|err| error(&err))?),
+    ColumnType::Counter => CqlValue::Counter(Counter(text.parse().map_err(|err| error(&err))?)),
+    _ => CqlValue::Text(text),
+  })
+}
+
+/// Accepts RFC 3339 date times (`2024-01-01T10:00:00Z`), the same without an offset, which is read as UTC, or
+/// plain dates, read as midnight UTC
+fn parse_timestamp(text: &str) -> Result<CqlTimestamp, chrono::ParseError> {
+  let date_time = DateTime::parse_from_rfc3339(text)
+    .map(|date_time| date_time.with_timezone(&Utc))
+    .or_else(|err| {
+      NaiveDateTime::from_str(text)
+        .or_else(|_| NaiveDate::from_str(text).map(|date| date.and_time(NaiveTime::MIN)))
+        .map(|date_time| date_time.and_utc())
+        .map_err(|_| err)
+    })?;
+
+  Ok(CqlTimestamp::from(date_time))
+}
+
+fn to_cql_decimal(decimal: BigDecimal) -> Result<CqlDecimal, &'static str> {
+  CqlDecimal::try_from(decimal).map_err(|_| "scale does not fit in 32 bits")
+}
+
+/// Name of the type as written in CQL, such as `map<text, list<int>>`
+pub(crate) fn cql_type_name(typ: &ColumnType) -> String {
+  match typ {
+    ColumnType::Custom(name) => format!("'{name}'"),
+    ColumnType::Ascii => "ascii".to_string(),
+    ColumnType::Boolean => "boolean".to_string(),
+    ColumnType::Blob => "blob".to_string(),
+    ColumnType::Counter => "counter".to_string(),
+    ColumnType::Date => "date".to_string(),
+    ColumnType::Decimal => "decimal".to_string(),
+    ColumnType::Double => "double".to_string(),
+    ColumnType::Duration => "duration".to_string(),
+    ColumnType::Float => "float".to_string(),
+    ColumnType::Int => "int".to_string(),
+    ColumnType::BigInt => "bigint".to_string(),
+    ColumnType::Text => "text".to_string(),
+    ColumnType::Timestamp => "timestamp".to_string(),
+    ColumnType::Inet => "inet".to_string(),
+    ColumnType::List(element_type) => format!("list<{}>", cql_type_name(element_type)),
+    ColumnType::Map(key_type, value_type) => format!(
+      "map<{}, {}>",
+      cql_type_name(key_type),
+      cql_type_name(value_type)
+    ),
+    ColumnType::Set(element_type) => format!("set<{}>", cql_type_name(element_type)),
+    ColumnType::UserDefinedType {
+      type_name,
+      keyspace,
+      ..
+    } => format!("{keyspace}.{type_name}"),
+    ColumnType::SmallInt => "smallint".to_string(),
+    ColumnType::TinyInt => "tinyint".to_string(),
+    ColumnType::Time => "time".to_string(),
+    ColumnType::Timeuuid => "timeuuid".to_string(),
+    ColumnType::Tuple(element_types) => format!(
+      "tuple<{}>",
+      element_types
+        .iter()
+        .map(cql_type_name)
+        .collect::<Vec<_>>()
+        .join(", ")
+    ),
+    ColumnType::Uuid => "uuid".to_string(),
+    ColumnType::Varint => "varint".to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn coerce(value: CqlValue, typ: ColumnType) -> Result<CqlValue, String> {
+    coerce_cql_value(value, &typ).map_err(|err| err.to_string())
+  }
+
+  fn text(text: &str) -> CqlValue {
+    CqlValue::Text(text.to_string())
+  }
+
+  fn udt_type() -> ColumnType {
+    ColumnType::UserDefinedType {
+      type_name: "address".to_string(),
+      keyspace: "ks".to_string(),
+      field_types: vec![
+        ("street".to_string(), ColumnType::Text),
+        ("number".to_string(), ColumnType::Int),
+      ],
+    }
+  }
+
+  fn udt(fields: Vec<(&str, Option<CqlValue>)>) -> CqlValue {
+    CqlValue::UserDefinedType {
+      keyspace: "keyspace".to_string(),
+      type_name: "type_name".to_string(),
+      fields: fields
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect(),
+    }
+  }
+
+  #[test]
+  fn integers_must_fit_their_type() {
+    assert_eq!(
+      coerce(CqlValue::Int(127), ColumnType::TinyInt),
+      Ok(CqlValue::TinyInt(127))
+    );
+    assert_eq!(
+      coerce(CqlValue::Int(-128), ColumnType::TinyInt),
+      Ok(CqlValue::TinyInt(-128))
+    );
+    assert!(
+      coerce(CqlValue::Int(128), ColumnType::TinyInt)
+        .unwrap_err()
+        .contains("value must be between -128 and 127")
+    );
+    assert_eq!(
+      coerce(CqlValue::Int(-32768), ColumnType::SmallInt),
+      Ok(CqlValue::SmallInt(-32768))
+    );
+    assert!(coerce(CqlValue::Int(32768), ColumnType::SmallInt).is_err());
+    assert_eq!(
+      coerce(CqlValue::BigInt(i32::MAX.into()), ColumnType::Int),
+      Ok(CqlValue::Int(i32::MAX))
+    );
+    assert!(coerce(CqlValue::BigInt(i64::from(i32::MAX) + 1), ColumnType::Int).is_err());
+    assert!(coerce(CqlValue::BigInt(i64::from(i32::MIN) - 1), ColumnType::Int).is_err());
+  }
+
+  #[test]
+  fn counters_and_times_must_be_in_range() {
+    assert_eq!(
+      coerce(CqlValue::BigInt(i64::MIN), ColumnType::Counter),
+      Ok(CqlValue::Counter(Counter(i64::MIN)))
+    );
+    assert_eq!(
+      coerce(CqlValue::Double(86_399_999_999_999.0), ColumnType::Time),
+      Ok(CqlValue::Time(CqlTime(86_399_999_999_999)))
+    );
+    assert!(coerce(CqlValue::BigInt(86_400_000_000_000), ColumnType::Time).is_err());
+    assert!(coerce(CqlValue::Int(-1), ColumnType::Time).is_err());
+  }
+
+  #[test]
+  fn doubles_must_be_safe_integers() {
+    assert_eq!(
+      coerce(CqlValue::Double(MAX_SAFE_INTEGER), ColumnType::BigInt),
+      Ok(CqlValue::BigInt(9_007_199_254_740_991))
+    );
+    assert!(
+      coerce(CqlValue::Double(MAX_SAFE_INTEGER + 1.0), ColumnType::BigInt)
+        .unwrap_err()
+        .contains("pass it as a BigInt instead")
+    );
+    assert!(
+      coerce(CqlValue::Double(1.5), ColumnType::Int)
+        .unwrap_err()
+        .contains("value must be an integer")
+    );
+    assert!(coerce(CqlValue::Double(f64::NAN), ColumnType::BigInt).is_err());
+  }
+
+  #[test]
+  fn floats_must_fit_in_32_bits() {
+    assert_eq!(
+      coerce(CqlValue::Double(1.5), ColumnType::Float),
+      Ok(CqlValue::Float(1.5))
+    );
+    assert_eq!(
+      coerce(CqlValue::Double(f64::INFINITY), ColumnType::Float),
+      Ok(CqlValue::Float(f32::INFINITY))
+    );
+    assert!(
+      coerce(CqlValue::Double(1e39), ColumnType::Float)
+        .unwrap_err()
+        .contains("value must be between")
+    );
+    assert!(coerce(CqlValue::Double(-1e39), ColumnType::Float).is_err());
+  }
+
+  #[test]
+  fn text_is_parsed_as_uuids() {
+    let uuid = uuid::Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
+
+    assert_eq!(
+      coerce(
+        text("123e4567-e89b-12d3-a456-426614174000"),
+        ColumnType::Uuid
+      ),
+      Ok(CqlValue::Uuid(uuid))
+    );
+    assert_eq!(
+      coerce(
+        text("123e4567-e89b-12d3-a456-426614174000"),
+        ColumnType::Timeuuid
+      ),
+      Ok(CqlValue::Timeuuid(CqlTimeuuid::from(uuid)))
+    );
+    assert!(
+      coerce(text("123e4567"), ColumnType::Uuid)
+        .unwrap_err()
+        .starts_with("Cannot convert Text(\"123e4567\") to uuid")
+    );
+    assert!(coerce(text("not a uuid"), ColumnType::Timeuuid).is_err());
+  }
+
+  #[test]
+  fn text_is_parsed_as_timestamps() {
+    let expected = Ok(CqlValue::Timestamp(CqlTimestamp(1_704_103_200_000)));
+
+    assert_eq!(
+      coerce(text("2024-01-01T10:00:00Z"), ColumnType::Timestamp),
+      expected
+    );
+    assert_eq!(
+      coerce(text("2024-01-01T11:00:00+01:00"), ColumnType::Timestamp),
+      expected
+    );
+    assert_eq!(
+      coerce(text("2024-01-01T10:00:00"), ColumnType::Timestamp),
+      expected
+    );
+    assert_eq!(
+      coerce(text("2024-01-01"), ColumnType::Timestamp),
+      Ok(CqlValue::Timestamp(CqlTimestamp(1_704_067_200_000)))
+    );
+    assert!(coerce(text("yesterday"), ColumnType::Timestamp).is_err());
+  }
+
+  #[test]
+  fn text_is_parsed_as_dates_and_times() {
+    assert_eq!(
+      coerce(text("1970-01-02"), ColumnType::Date),
+      Ok(CqlValue::Date(CqlDate((1 << 31) + 1)))
+    );
+    assert!(coerce(text("2023-02-29"), ColumnType::Date).is_err());
+    assert_eq!(
+      coerce(text("00:00:01.5"), ColumnType::Time),
+      Ok(CqlValue::Time(CqlTime(1_500_000_000)))
+    );
+    assert!(coerce(text("24:00:00"), ColumnType::Time).is_err());
+  }
+
+  #[test]
+  fn text_is_parsed_as_decimals_and_inets() {
+    assert_eq!(
+      coerce(text("-1.50"), ColumnType::Decimal),
+      Ok(CqlValue::Decimal(
+        CqlDecimal::from_signed_be_bytes_and_exponent(vec![0xff, 0x6a], 2)
+      ))
+    );
+    assert!(coerce(text("1.5.0"), ColumnType::Decimal).is_err());
+    assert_eq!(
+      coerce(text("::1"), ColumnType::Inet),
+      Ok(CqlValue::Inet("::1".parse().unwrap()))
+    );
+    assert_eq!(
+      coerce(text("127.0.0.1"), ColumnType::Inet),
+      Ok(CqlValue::Inet("127.0.0.1".parse().unwrap()))
+    );
+    assert!(coerce(text("256.0.0.1"), ColumnType::Inet).is_err());
+  }
+
+  #[test]
+  fn collections_are_coerced_element_wise() {
+    assert_eq!(
+      coerce(
+        CqlValue::List(vec![
+          CqlValue::List(vec![CqlValue::Int(1)]),
+          CqlValue::List(vec![]),
+        ]),
+        ColumnType::Set(Box::new(ColumnType::List(Box::new(ColumnType::BigInt)))),
+      ),
+      Ok(CqlValue::Set(vec![
+        CqlValue::List(vec![CqlValue::BigInt(1)]),
+        CqlValue::List(vec![]),
+      ]))
+    );
+    assert!(
+      coerce(
+        CqlValue::List(vec![CqlValue::List(vec![CqlValue::Int(128)])]),
+        ColumnType::List(Box::new(ColumnType::List(Box::new(ColumnType::TinyInt)))),
+      )
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn udt_fields_are_sent_in_type_order() {
+    assert_eq!(
+      coerce(
+        udt(vec![
+          ("number", Some(CqlValue::Int(1))),
+          ("street", Some(text("Main"))),
+        ]),
+        udt_type()
+      ),
+      Ok(CqlValue::UserDefinedType {
+        keyspace: "ks".to_string(),
+        type_name: "address".to_string(),
+        fields: vec![
+          ("street".to_string(), Some(text("Main"))),
+          ("number".to_string(), Some(CqlValue::Int(1))),
+        ],
+      })
+    );
+    assert_eq!(
+      coerce(udt(vec![("number", Some(CqlValue::Int(1)))]), udt_type()),
+      Ok(CqlValue::UserDefinedType {
+        keyspace: "ks".to_string(),
+        type_name: "address".to_string(),
+        fields: vec![
+          ("street".to_string(), None),
+          ("number".to_string(), Some(CqlValue::Int(1))),
+        ],
+      })
+    );
+  }
+
+  #[test]
+  fn udt_fields_must_exist_in_the_type() {
+    assert!(
+      coerce(
+        udt(vec![("strete", Some(text("Main"))), ("number", None)]),
+        udt_type()
+      )
+      .unwrap_err()
+      .contains("the type has no field named `strete`")
+    );
+  }
+}
@@ -1,4 +1,5 @@
 pub mod cql_value_bridge;
+pub mod cql_value_coercion;
 pub mod query_parameter;
 pub mod query_results;
 pub mod to_cql_value;
@@ -1,15 +1,157 @@
+use std::fmt::Display;
+
+use napi::Either;
+use scylla::frame::response::result::{ColumnSpec, ColumnType};
+use scylla::frame::value::MaybeUnset;
 use scylla::serialize::{
   row::{RowSerializationContext, SerializeRow},
   value::SerializeCql,
   RowWriter, SerializationError,
 };
 
-use super::{cql_value_bridge::ParameterWithMapType, to_cql_value::ToCqlValue};
+use super::{
+  cql_value_bridge::{js_type_name, JSQueryParameters, ParameterValue, ParameterWithMapType},
+  cql_value_coercion::{coerce_cql_value, cql_type_name, CoercionError},
+  to_cql_value::ToCqlValue,
+};
 
 #[derive(Debug, Clone)]
 pub struct QueryParameter<'a> {
   #[allow(clippy::type_complexity)]
-  pub(crate) parameters: Option<Vec<ParameterWithMapType<'a>>>,
+  pub(crate) parameters: Option<JSQueryParameters<'a>>,
+}
+
+/// Parameters that cannot be bound to the markers of the statement
+#[derive(Debug, Clone)]
+pub enum BindError {
+  ParameterCountMismatch {
+    expected: usize,
+    received: usize,
+  },
+  MissingNamedParameter(String),
+  UnknownNamedParameters(Vec<String>),
+  InvalidParameter {
+    index: usize,
+    name: String,
+    expected: String,
+    received: &'static str,
+    reason: String,
+  },
+}
+
+impl Display for BindError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      BindError::ParameterCountMismatch { expected, received } => write!(
+        f,
+        "The statement has {expected} bind markers but {received} parameters were given"
+      ),
+      BindError::MissingNamedParameter(name) => {
+        write!(f, "Missing value for the named parameter `{name}`")
+      }
+      BindError::UnknownNamedParameters(names) => write!(
+        f,
+        "The statement has no bind marker named {}",
+        names
+          .iter()
+          .map(|name| format!("`{name}`"))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+      BindError::InvalidParameter {
+        index,
+        name,
+        expected,
+        received,
+        reason,
+      } => write!(
+        f,
+        "Invalid parameter at index {index} (`{name}`): expected {expected}, received {received}. {reason}"
+      ),
+    }
+  }
+}
+
+impl std::error::Error for BindError {}
+
+/// Reason why a single parameter could not be serialized
+enum ValueError {
+  /// The value cannot be represented as the type of its bind marker
+  Coercion(CoercionError),
+  /// The driver failed to write the value, e.g. a collection too large for the protocol
+  Serialization(SerializationError),
+}
+
+impl ValueError {
+  fn reason(&self) -> String {
+    match self {
+      ValueError::Coercion(err) => err.to_string(),
+      ValueError::Serialization(err) => format!("The driver failed to write it: {err}"),
+    }
+  }
+}
+
+impl From<CoercionError> for ValueError {
+  fn from(err: CoercionError) -> Self {
+    ValueError::Coercion(err)
+  }
+}
+
+impl From<SerializationError> for ValueError {
+  fn from(err: SerializationError) -> Self {
+    ValueError::Serialization(err)
+  }
+}
+
+fn serialize_value(
+  parameter: &ParameterValue<'_>,
+  typ: &ColumnType,
+  writer: &mut RowWriter,
+) -> Result<(), ValueError> {
+  let value = match parameter {
+    // Serialized straight from the bytes received from JS, without copying them into a `CqlValue`
+    Some(Either::B(ParameterWithMapType::H(blob))) if matches!(typ, ColumnType::Blob) => {
+      blob.as_bytes().serialize(typ, writer.make_cell_writer())?;
+      return Ok(());
+    }
+    None => MaybeUnset::Set(None),
+    Some(Either::A(_unset)) => MaybeUnset::Unset,
+    Some(Either::B(parameter)) => {
+      MaybeUnset::Set(Some(coerce_cql_value(parameter.to_cql_value(), typ)?))
+    }
+  };
+  value.serialize(typ, writer.make_cell_writer())?;
+
+  Ok(())
+}
+
+/// Whether `key` names the bind marker `name`. Like unquoted CQL identifiers, keys are case insensitive: the
+/// server lowercases `:userId` to `userid`, while a quoted marker such as `:"userId"` keeps its case and only
+/// matches the exact key
+fn is_marker_key(key: &str, name: &str) -> bool {
+  key == name || key.to_lowercase() == name
+}
+
+/// Serializes the parameter bound to the `index`-th marker, naming the marker in the error when it fails
+fn bind_value(
+  index: usize,
+  parameter: &ParameterValue<'_>,
+  column: &ColumnSpec,
+  writer: &mut RowWriter,
+) -> Result<(), SerializationError> {
+  serialize_value(parameter, &column.typ, writer).map_err(|err| {
+    SerializationError::new(BindError::InvalidParameter {
+      index,
+      name: column.name.clone(),
+      expected: cql_type_name(&column.typ),
+      received: match parameter {
+        Some(Either::B(parameter)) => js_type_name(parameter),
+        Some(Either::A(_unset)) => "UNSET",
+        None => "null",
+      },
+      reason: err.reason(),
+    })
+  })
 }
 
 impl<'a> SerializeRow for QueryParameter<'a> {
@@ -18,37 +160,224 @@ impl<'a> SerializeRow for QueryParameter<'a> {
     ctx: &RowSerializationContext<'_>,
     writer: &mut RowWriter,
   ) -> Result<(), SerializationError> {
-    if let Some(parameters) = &self.parameters {
-      for (i, parameter) in parameters.iter().enumerate() {
-        parameter
-          .to_cql_value()
-          .serialize(&ctx.columns()[i].typ, writer.make_cell_writer())?;
-      }
-    }
-    Ok(())
+    self.write_row(ctx.columns(), writer)
   }
 
   fn is_empty(&self) -> bool {
-    self.parameters.is_none() || self.parameters.as_ref().unwrap().is_empty()
+    match &self.parameters {
+      Some(Either::A(parameters)) => parameters.is_empty(),
+      Some(Either::B(parameters)) => parameters.is_empty(),
+      None => true,
+    }
   }
 }
 
 impl<'a> QueryParameter<'a> {
-  #[allow(clippy::type_complexity)]
-  pub fn parser(parameters: Option<Vec<ParameterWithMapType<'a>>>) -> Option<Self> {
-    if parameters.is_none() {
-      return Some(QueryParameter { parameters: None });
+  /// Serializes the parameters bound to the markers described by `columns`
+  pub(crate) fn write_row(
+    &self,
+    columns: &[ColumnSpec],
+    writer: &mut RowWriter,
+  ) -> Result<(), SerializationError> {
+    match &self.parameters {
+      Some(Either::A(parameters)) => {
+        if parameters.len() != columns.len() {
+          return Err(SerializationError::new(BindError::ParameterCountMismatch {
+            expected: columns.len(),
+            received: parameters.len(),
+          }));
+        }
+
+        for (index, (parameter, column)) in parameters.iter().zip(columns).enumerate() {
+          bind_value(index, parameter, column, writer)?;
+        }
+      }
+      Some(Either::B(parameters)) => {
+        let mut unknown = parameters
+          .keys()
+          .filter(|key| {
+            !columns
+              .iter()
+              .any(|column| is_marker_key(key, &column.name))
+          })
+          .cloned()
+          .collect::<Vec<_>>();
+        unknown.sort_unstable();
+        if !unknown.is_empty() {
+          return Err(SerializationError::new(BindError::UnknownNamedParameters(
+            unknown,
+          )));
+        }
+
+        for (index, column) in columns.iter().enumerate() {
+          // A key with the exact name of the marker wins over the ones only differing by case
+          let parameter = parameters
+            .get(&column.name)
+            .or_else(|| {
+              parameters
+                .iter()
+                .find(|(key, _)| is_marker_key(key, &column.name))
+                .map(|(_, parameter)| parameter)
+            })
+            .ok_or_else(|| {
+              SerializationError::new(BindError::MissingNamedParameter(column.name.clone()))
+            })?;
+          bind_value(index, parameter, column, writer)?;
+        }
+      }
+      None if !columns.is_empty() => {
+        return Err(SerializationError::new(BindError::ParameterCountMismatch {
+          expected: columns.len(),
+          received: 0,
+        }));
+      }
+      None => {}
     }
+    Ok(())
+  }
+
+  /// Wraps the parameters of a request, they are only checked against the bind markers when serialized
+  pub fn parser(parameters: Option<JSQueryParameters<'a>>) -> Self {
+    QueryParameter { parameters }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use scylla::frame::response::result::TableSpec;
+
+  use super::*;
 
-    let parameters = parameters.unwrap();
+  fn columns(names: &[&str]) -> Vec<ColumnSpec> {
+    names
+      .iter()
+      .map(|name| ColumnSpec {
+        table_spec: TableSpec::borrowed("ks", "tab"),
+        name: name.to_string(),
+        typ: ColumnType::Int,
+      })
+      .collect()
+  }
 
-    let mut params = Vec::with_capacity(parameters.len());
-    for parameter in parameters {
-      params.push(parameter);
+  fn named(parameters: &[(&str, f64)]) -> QueryParameter<'static> {
+    QueryParameter {
+      parameters: Some(Either::B(
+        parameters
+          .iter()
+          .map(|(key, value)| {
+            (
+              key.to_string(),
+              Some(Either::B(ParameterWithMapType::A(*value))),
+            )
+          })
+          .collect::<HashMap<_, _>>(),
+      )),
     }
+  }
 
-    Some(QueryParameter {
-      parameters: Some(params),
-    })
+  fn write_row(parameters: &QueryParameter, columns: &[ColumnSpec]) -> Result<Vec<u8>, String> {
+    let mut buffer = Vec::new();
+    parameters
+      .write_row(columns, &mut RowWriter::new(&mut buffer))
+      .map_err(|err| err.to_string())?;
+    Ok(buffer)
+  }
+
+  /// Serialized `int` cells, each prefixed by its length
+  fn int_cells(values: &[i32]) -> Vec<u8> {
+    values
+      .iter()
+      .flat_map(|value| [4i32.to_be_bytes(), value.to_be_bytes()].concat())
+      .collect()
+  }
+
+  #[test]
+  fn missing_parameters_are_rejected_when_there_are_markers() {
+    let parameters = QueryParameter { parameters: None };
+
+    assert_eq!(write_row(&parameters, &columns(&[])), Ok(vec![]));
+    assert!(
+      write_row(&parameters, &columns(&["a", "b"]))
+        .unwrap_err()
+        .contains("The statement has 2 bind markers but 0 parameters were given")
+    );
+  }
+
+  #[test]
+  fn parameter_count_must_match_the_markers() {
+    let parameters = QueryParameter {
+      parameters: Some(Either::A(vec![])),
+    };
+
+    assert!(
+      write_row(&parameters, &columns(&["a"]))
+        .unwrap_err()
+        .contains("The statement has 1 bind markers but 0 parameters were given")
+    );
+  }
+
+  #[test]
+  fn named_parameters_are_bound_in_marker_order() {
+    let parameters = named(&[("b", 2.0), ("a", 1.0)]);
+
+    assert_eq!(
+      write_row(&parameters, &columns(&["a", "b"])),
+      Ok(int_cells(&[1, 2]))
+    );
+  }
+
+  #[test]
+  fn named_parameters_must_all_be_given() {
+    let parameters = named(&[("a", 1.0)]);
+
+    assert!(
+      write_row(&parameters, &columns(&["a", "b"]))
+        .unwrap_err()
+        .contains("Missing value for the named parameter `b`")
+    );
+  }
+
+  #[test]
+  fn named_parameters_must_match_a_marker() {
+    let parameters = named(&[("a", 1.0), ("c", 3.0), ("d", 4.0)]);
+
+    assert!(
+      write_row(&parameters, &columns(&["a"]))
+        .unwrap_err()
+        .contains("The statement has no bind marker named `c`, `d`")
+    );
+  }
+
+  #[test]
+  fn named_parameters_match_unquoted_markers_case_insensitively() {
+    // `:userId` is returned as `userid` by the server
+    assert_eq!(
+      write_row(&named(&[("userId", 1.0)]), &columns(&["userid"])),
+      Ok(int_cells(&[1]))
+    );
+    // The exact name wins over a key only differing by case
+    assert_eq!(
+      write_row(
+        &named(&[("userId", 1.0), ("userid", 2.0)]),
+        &columns(&["userid"])
+      ),
+      Ok(int_cells(&[2]))
+    );
+  }
+
+  #[test]
+  fn named_parameters_match_quoted_markers_exactly() {
+    // `:"userId"` keeps its case
+    assert_eq!(
+      write_row(&named(&[("userId", 1.0)]), &columns(&["userId"])),
+      Ok(int_cells(&[1]))
+    );
+    assert!(
+      write_row(&named(&[("userid", 1.0)]), &columns(&["userId"]))
+        .unwrap_err()
+        .contains("The statement has no bind marker named `userid`")
+    );
   }
 }
@@ -1,20 +1,25 @@
 use std::collections::HashMap;
+use std::ptr;
 
-use napi::bindgen_prelude::{BigInt, Either10, Either11, Either9};
-use scylla::frame::response::result::{ColumnType, CqlValue};
+use chrono::{DateTime, Utc};
+use napi::bindgen_prelude::{BigInt, Buffer, Either12, ToNapiValue};
+use napi::{check_status, sys};
+use scylla::frame::response::result::{ColumnSpec, ColumnType, CqlValue, Row};
 
-use crate::types::{decimal::Decimal, duration::Duration, uuid::Uuid};
+use crate::error::{ErrorKind, ScyllaError};
+use crate::types::{
+  decimal::Decimal, duration::Duration, local_date::LocalDate, local_time::LocalTime, uuid::Uuid,
+};
 pub struct QueryResult {
   pub(crate) result: scylla::QueryResult,
 }
 
 macro_rules! define_return_type {
     ($($t:ty),+) => {
-      type BaseTypes = Either9<$($t),+>;
-      type NativeTypes = Either10<$($t),+, Vec<BaseTypes>>;
-      pub type WithMapType = Either11<$($t),+, Vec<BaseTypes>, HashMap<String, NativeTypes>>;
+      type BaseTypes = Either12<$($t),+>;
       type ReturnType = napi::Result<Option<WithMapType>>;
-      pub type JSQueryResult = napi::Result<Vec<HashMap<String, WithMapType>>>;
+      pub type JSQueryRow = HashMap<String, Option<WithMapType>>;
+      pub type JSQueryResult = napi::Result<Vec<JSQueryRow>>;
     };
 }
 
@@ -27,10 +32,164 @@ define_return_type!(
   Uuid,
   Duration,
   Decimal,
-  Vec<u8>
+  Buffer,
+  DateTime<Utc>,
+  LocalDate,
+  LocalTime
 );
 
+/// Value of a column, collections, tuples and UDTs hold other values and can be nested to any depth
+#[derive(Clone)]
+pub enum WithMapType {
+  Base(BaseTypes),
+  /// Lists, sets and tuples, only the elements of a tuple can be `null`
+  List(Vec<Option<WithMapType>>),
+  /// Maps with text keys and UDTs, returned as plain objects. Only the fields of a UDT can be `null`
+  Map(HashMap<String, Option<WithMapType>>),
+  /// Maps with keys of any other type, returned as a JS `Map` so the keys keep their type
+  Entries(Vec<(WithMapType, WithMapType)>),
+}
+
+impl From<BaseTypes> for WithMapType {
+  fn from(value: BaseTypes) -> Self {
+    Self::Base(value)
+  }
+}
+
+impl ToNapiValue for WithMapType {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> napi::Result<sys::napi_value> {
+    match val {
+      WithMapType::Base(value) => unsafe { BaseTypes::to_napi_value(env, value) },
+      WithMapType::List(values) => unsafe { Vec::to_napi_value(env, values) },
+      WithMapType::Map(entries) => unsafe { HashMap::to_napi_value(env, entries) },
+      WithMapType::Entries(entries) => {
+        let entries = entries
+          .into_iter()
+          .map(|(key, value)| vec![key, value])
+          .collect::<Vec<_>>();
+        let entries = unsafe { Vec::to_napi_value(env, entries)? };
+
+        let mut global = ptr::null_mut();
+        check_status!(
+          unsafe { sys::napi_get_global(env, &mut global) },
+          "Failed to get the global object"
+        )?;
+        let mut map_constructor = ptr::null_mut();
+        check_status!(
+          unsafe {
+            sys::napi_get_named_property(env, global, c"Map".as_ptr(), &mut map_constructor)
+          },
+          "Failed to get the Map constructor"
+        )?;
+        let mut map = ptr::null_mut();
+        check_status!(
+          unsafe { sys::napi_new_instance(env, map_constructor, 1, [entries].as_ptr(), &mut map) },
+          "Failed to create a Map"
+        )?;
+
+        Ok(map)
+      }
+    }
+  }
+}
+
+/// A single page of a result, along with the state needed to fetch the following one
+#[napi(object, object_from_js = false)]
+pub struct PagedResult {
+  pub rows: Vec<JSQueryRow>,
+  /// Opaque paging state to be handed back to `executePaged` to fetch the next page.\
+  /// It is absent when the current page is the last one.
+  pub paging_state: Option<Buffer>,
+}
+
+/// Result of a conditional statement (`INSERT ... IF NOT EXISTS`, `UPDATE ... IF ...`) or of a batch of them
+#[napi(object, object_from_js = false)]
+pub struct ConditionalResult {
+  /// Whether the condition held and the statement was applied
+  pub applied: bool,
+  /// When the statement was not applied, the values that made the condition fail: the existing row for
+  /// `IF NOT EXISTS`, the columns checked by the condition otherwise
+  pub existing: Option<JSQueryRow>,
+}
+
+/// Name of the column added by the database to the result of conditional statements
+const APPLIED_COLUMN: &str = "[applied]";
+
+impl ConditionalResult {
+  /// Whether `statement` has a condition, an `IF` keyword outside of string literals and quoted names
+  pub(crate) fn is_conditional(statement: &str) -> bool {
+    let mut quote = None;
+    let mut word = String::new();
+
+    for c in statement.chars().chain(std::iter::once(' ')) {
+      match quote {
+        Some(q) if c == q => quote = None,
+        Some(_) => {}
+        None if c.is_ascii_alphanumeric() || c == '_' => {
+          word.push(c);
+          continue;
+        }
+        None if c == '\'' || c == '"' => quote = Some(c),
+        None => {}
+      }
+
+      if word.eq_ignore_ascii_case("IF") {
+        return true;
+      }
+      word.clear();
+    }
+
+    false
+  }
+
+  /// Rejects statements without a condition before they are sent, since they would always be applied
+  pub(crate) fn ensure_conditional(
+    is_conditional: bool,
+    statement: impl std::fmt::Display,
+  ) -> napi::Result<()> {
+    if is_conditional {
+      return Ok(());
+    }
+
+    Err(
+      ScyllaError::new(
+        ErrorKind::InvalidQueryError,
+        format!("The statement is not conditional, it needs an `IF` condition - [{statement}]"),
+      )
+      .into(),
+    )
+  }
+
+  pub(crate) fn from_rows(rows: Vec<JSQueryRow>) -> Self {
+    let mut row = rows.into_iter().next().unwrap_or_default();
+
+    // Statements that do not report it, like `CREATE TABLE ... IF NOT EXISTS`, were applied when they
+    // succeeded
+    let applied = match row.remove(APPLIED_COLUMN) {
+      Some(Some(WithMapType::Base(BaseTypes::D(applied)))) => applied,
+      _ => true,
+    };
+
+    Self {
+      applied,
+      existing: Some(row).filter(|row| !applied && !row.is_empty()),
+    }
+  }
+}
+
 impl QueryResult {
+  pub fn paged_parser(mut result: scylla::QueryResult) -> napi::Result<PagedResult> {
+    let paging_state = result
+      .paging_state
+      .take()
+      .map(|paging_state| Buffer::from(paging_state.to_vec()));
+
+    Ok(PagedResult {
+      rows: Self::parser(result)?,
+      paging_state,
+    })
+  }
+
   pub fn parser(result: scylla::QueryResult) -> JSQueryResult {
     if result.result_not_rows().is_ok() || result.rows.is_none() {
       return Ok(Default::default());
@@ -39,174 +198,178 @@ impl QueryResult {
     let rows = result.rows.unwrap();
     let column_specs = result.col_specs;
 
-    let mut result_json: Vec<HashMap<String, WithMapType>> = vec![];
-
-    for row in rows {
-      let mut row_object: HashMap<String, WithMapType> = HashMap::new();
+    rows
+      .into_iter()
+      .map(|row| Self::parse_row(row, &column_specs))
+      .collect()
+  }
 
-      for (i, column) in row.columns.iter().enumerate() {
-        let column_name = column_specs[i].name.clone();
-        let column_value = Self::parse_value(column, &column_specs[i].typ)?;
-        if let Some(column_value) = column_value {
-          row_object.insert(column_name, column_value);
-        }
-      }
+  // Rows are taken by value: blobs are handed to JS without being copied again and collections are not cloned
+  pub(crate) fn parse_row(row: Row, column_specs: &[ColumnSpec]) -> napi::Result<JSQueryRow> {
+    let mut row_object: JSQueryRow = HashMap::new();
 
-      result_json.push(row_object);
+    for (i, column) in row.columns.into_iter().enumerate() {
+      let column_name = column_specs[i].name.clone();
+      let column_value = Self::parse_value(column, &column_specs[i].typ)?;
+      row_object.insert(column_name, column_value);
     }
 
-    Ok(result_json)
+    Ok(row_object)
   }
 
-  fn parse_value(column: &Option<CqlValue>, column_type: &ColumnType) -> ReturnType {
+  fn parse_value(column: Option<CqlValue>, column_type: &ColumnType) -> ReturnType {
     column
-      .as_ref()
       .map(|column| match column_type {
-        ColumnType::Ascii => Ok(WithMapType::A(column.as_ascii().unwrap().to_string())),
-        ColumnType::Text => Ok(WithMapType::A(column.as_text().unwrap().to_string())),
-        ColumnType::Uuid => Ok(WithMapType::F(Uuid {
-          uuid: column.as_uuid().unwrap(),
-        })),
-        ColumnType::BigInt => Ok(WithMapType::E(column.as_bigint().unwrap().into())),
-        ColumnType::Int => Ok(WithMapType::B(column.as_int().unwrap() as i64)),
-        ColumnType::Float => Ok(WithMapType::C(column.as_float().unwrap() as f64)),
-        ColumnType::Double => Ok(WithMapType::C(column.as_double().unwrap())),
-        ColumnType::Boolean => Ok(WithMapType::D(column.as_boolean().unwrap())),
-        ColumnType::SmallInt => Ok(WithMapType::B(column.as_smallint().unwrap() as i64)),
-        ColumnType::TinyInt => Ok(WithMapType::B(column.as_tinyint().unwrap() as i64)),
-        ColumnType::Date | ColumnType::Timestamp => {
-          Ok(WithMapType::A(column.as_date().unwrap().to_string()))
+        ColumnType::Ascii | ColumnType::Text => {
+          Ok(BaseTypes::A(column.into_string().unwrap()).into())
         }
-        ColumnType::Inet => Ok(WithMapType::A(column.as_inet().unwrap().to_string())),
-        ColumnType::Duration => Ok(WithMapType::G(column.as_cql_duration().unwrap().into())),
-        ColumnType::Decimal => Ok(WithMapType::H(
-          column.clone().into_cql_decimal().unwrap().into(),
-        )),
-        ColumnType::Blob => Ok(WithMapType::I(column.as_blob().unwrap().clone())),
-        ColumnType::Counter => Ok(WithMapType::B(column.as_counter().unwrap().0)),
-        ColumnType::Varint => Ok(WithMapType::I(
-          column
-            .clone()
-            .into_cql_varint()
-            .unwrap()
-            .as_signed_bytes_be_slice()
-            .into(),
-        )),
-        ColumnType::Time => Ok(WithMapType::B(column.as_time().unwrap().nanosecond() as i64)),
-        ColumnType::Timeuuid => Ok(WithMapType::F(column.as_timeuuid().unwrap().into())),
-        ColumnType::Map(key, value) => {
-          let map = column
-            .as_map()
+        ColumnType::Uuid => Ok(
+          BaseTypes::F(Uuid {
+            uuid: column.as_uuid().unwrap(),
+          })
+          .into(),
+        ),
+        ColumnType::BigInt => Ok(BaseTypes::E(column.as_bigint().unwrap().into()).into()),
+        ColumnType::Int => Ok(BaseTypes::B(column.as_int().unwrap() as i64).into()),
+        ColumnType::Float => Ok(BaseTypes::C(column.as_float().unwrap() as f64).into()),
+        ColumnType::Double => Ok(BaseTypes::C(column.as_double().unwrap()).into()),
+        ColumnType::Boolean => Ok(BaseTypes::D(column.as_boolean().unwrap()).into()),
+        ColumnType::SmallInt => Ok(BaseTypes::B(column.as_smallint().unwrap() as i64).into()),
+        ColumnType::TinyInt => Ok(BaseTypes::B(column.as_tinyint().unwrap() as i64).into()),
+        ColumnType::Timestamp => Ok(
+          BaseTypes::J(column.as_cql_timestamp().unwrap().try_into().map_err(|_| {
+            napi::Error::new(
+              napi::Status::GenericFailure,
+              "Timestamp is out of the range supported by Date",
+            )
+          })?)
+          .into(),
+        ),
+        ColumnType::Date => Ok(BaseTypes::K(column.as_cql_date().unwrap().into()).into()),
+        ColumnType::Inet => Ok(BaseTypes::A(column.as_inet().unwrap().to_string()).into()),
+        ColumnType::Duration => Ok(BaseTypes::G(column.as_cql_duration().unwrap().into()).into()),
+        ColumnType::Decimal => Ok(BaseTypes::H(column.into_cql_decimal().unwrap().into()).into()),
+        ColumnType::Blob => Ok(BaseTypes::I(column.into_blob().unwrap().into()).into()),
+        ColumnType::Counter => Ok(BaseTypes::B(column.as_counter().unwrap().0).into()),
+        ColumnType::Varint => Ok(
+          BaseTypes::I(
+            column
+              .into_cql_varint()
+              .unwrap()
+              .as_signed_bytes_be_slice()
+              .to_vec()
+              .into(),
+          )
+          .into(),
+        ),
+        ColumnType::Time => Ok(BaseTypes::L(column.as_cql_time().unwrap().into()).into()),
+        ColumnType::Timeuuid => Ok(BaseTypes::F(column.as_timeuuid().unwrap().into()).into()),
+        ColumnType::Map(key_type, value_type) => {
+          let entries = column
+            .into_pair_vec()
             .unwrap()
-            .iter()
-            .map(|(k, v)| {
-              let key = Self::parse_value(&Some(k.clone()), key).unwrap();
-              let value =
-                Self::remove_map_from_type(Self::parse_value(&Some(v.clone()), value).unwrap())?
-                  .unwrap();
-              key
-                .map(|key| match key {
-                  WithMapType::A(key) => Ok((key, value)),
-                  _ => Err(napi::Error::new(
-                    napi::Status::GenericFailure,
-                    "Map key must be a string",
-                  )),
-                })
-                .transpose()
+            .into_iter()
+            .map(|(key, value)| {
+              Ok((
+                Self::parse_value(Some(key), key_type)?.unwrap(),
+                Self::parse_value(Some(value), value_type)?.unwrap(),
+              ))
             })
-            .collect::<napi::Result<Option<HashMap<String, NativeTypes>>>>();
+            .collect::<napi::Result<Vec<_>>>()?;
 
-          Ok(WithMapType::K(map?.unwrap()))
+          match key_type.as_ref() {
+            ColumnType::Text | ColumnType::Ascii => Ok(WithMapType::Map(
+              entries
+                .into_iter()
+                .map(|(key, value)| match key {
+                  WithMapType::Base(BaseTypes::A(key)) => (key, Some(value)),
+                  _ => unreachable!(),
+                })
+                .collect(),
+            )),
+            _ => Ok(WithMapType::Entries(entries)),
+          }
         }
-        ColumnType::UserDefinedType { field_types, .. } => Ok(WithMapType::K(Self::parse_udt(
-          column.as_udt().unwrap(),
+        ColumnType::UserDefinedType { field_types, .. } => Ok(WithMapType::Map(Self::parse_udt(
+          column.into_udt_pair_vec().unwrap(),
           field_types,
         )?)),
-        ColumnType::List(list_type) => Ok(WithMapType::J(Self::extract_base_types(
-          column
-            .as_list()
-            .unwrap()
-            .iter()
-            .map(|e| Self::parse_value(&Some(e.clone()), list_type))
-            .collect::<Vec<ReturnType>>(),
-        )?)),
-        ColumnType::Set(set_type) => Ok(WithMapType::J(Self::extract_base_types(
-          column
-            .as_set()
-            .unwrap()
-            .iter()
-            .map(|e| Self::parse_value(&Some(e.clone()), set_type))
-            .collect::<Vec<ReturnType>>(),
-        )?)),
-        ColumnType::Custom(_) => Ok(WithMapType::A(
-          "ColumnType Custom not supported yet".to_string(),
-        )),
-        ColumnType::Tuple(_) => Ok(WithMapType::A(
-          "ColumnType Tuple not supported yet".to_string(),
+        ColumnType::List(element_type) | ColumnType::Set(element_type) => Ok(WithMapType::List(
+          Self::parse_elements(column.into_vec().unwrap(), element_type)?,
         )),
+        ColumnType::Tuple(element_types) => match column {
+          // Trailing elements can be omitted by the server, they are returned as `null`
+          CqlValue::Tuple(elements) => {
+            let mut elements = elements.into_iter();
+            Ok(WithMapType::List(
+              element_types
+                .iter()
+                .map(|element_type| Self::parse_value(elements.next().flatten(), element_type))
+                .collect::<napi::Result<_>>()?,
+            ))
+          }
+          _ => unreachable!(),
+        },
+        ColumnType::Custom(_) => {
+          Ok(BaseTypes::A("ColumnType Custom not supported yet".to_string()).into())
+        }
       })
       .transpose()
   }
 
+  fn parse_elements(
+    elements: Vec<CqlValue>,
+    element_type: &ColumnType,
+  ) -> napi::Result<Vec<Option<WithMapType>>> {
+    elements
+      .into_iter()
+      .map(|element| Self::parse_value(Some(element), element_type))
+      .collect()
+  }
+
   fn parse_udt(
-    udt: &[(String, Option<CqlValue>)],
+    udt: Vec<(String, Option<CqlValue>)>,
     field_types: &[(String, ColumnType)],
-  ) -> napi::Result<HashMap<String, NativeTypes>> {
-    let mut result: HashMap<String, NativeTypes> = HashMap::new();
-
-    for (i, (field_name, field_value)) in udt.iter().enumerate() {
-      let field_type = &field_types[i].1;
-      let parsed_value = Self::parse_value(field_value, field_type);
-      if let Some(parsed_value) = Self::remove_map_from_type(parsed_value?)? {
-        result.insert(field_name.clone(), parsed_value);
-      }
-    }
-
-    Ok(result)
+  ) -> napi::Result<HashMap<String, Option<WithMapType>>> {
+    // Fields added to the type after the value was written are missing from it, they are returned as `null`
+    let mut udt = udt.into_iter();
+    field_types
+      .iter()
+      .map(|(field_name, field_type)| {
+        let field_value = udt.next().and_then(|(_, value)| value);
+        Ok((
+          field_name.clone(),
+          Self::parse_value(field_value, field_type)?,
+        ))
+      })
+      .collect()
   }
+}
 
-  fn remove_map_from_type(a: Option<WithMapType>) -> napi::Result<Option<NativeTypes>> {
-    a.map(|f| match f {
-      WithMapType::A(a) => Ok(NativeTypes::A(a)),
-      WithMapType::B(a) => Ok(NativeTypes::B(a)),
-      WithMapType::C(a) => Ok(NativeTypes::C(a)),
-      WithMapType::D(a) => Ok(NativeTypes::D(a)),
-      WithMapType::E(a) => Ok(NativeTypes::E(a)),
-      WithMapType::F(a) => Ok(NativeTypes::F(a)),
-      WithMapType::G(a) => Ok(NativeTypes::G(a)),
-      WithMapType::H(a) => Ok(NativeTypes::H(a)),
-      WithMapType::I(a) => Ok(NativeTypes::I(a)),
-      WithMapType::J(a) => Ok(NativeTypes::J(a)),
-      WithMapType::K(_) => Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        "Map type is not supported in this context".to_string(),
-      )),
-    })
-    .transpose()
+#[cfg(test)]
+mod tests {
+  use super::ConditionalResult;
+
+  #[test]
+  fn detects_conditions() {
+    assert!(ConditionalResult::is_conditional(
+      "INSERT INTO users (id) VALUES (?) IF NOT EXISTS"
+    ));
+    assert!(ConditionalResult::is_conditional(
+      "update users set name = ? where id = ? if name = ?"
+    ));
+    assert!(ConditionalResult::is_conditional(
+      "DELETE FROM users WHERE id = 1 IF EXISTS"
+    ));
   }
 
-  fn extract_base_types(return_types: Vec<ReturnType>) -> napi::Result<Vec<BaseTypes>> {
-    return_types
-      .into_iter()
-      .filter_map(|return_type| {
-        return_type.ok().and_then(|opt_with_map_type| {
-          opt_with_map_type.map(|with_map_type| match with_map_type {
-            WithMapType::A(a) => Ok(BaseTypes::A(a)),
-            WithMapType::B(b) => Ok(BaseTypes::B(b)),
-            WithMapType::C(c) => Ok(BaseTypes::C(c)),
-            WithMapType::D(d) => Ok(BaseTypes::D(d)),
-            WithMapType::E(e) => Ok(BaseTypes::E(e)),
-            WithMapType::F(f) => Ok(BaseTypes::F(f)),
-            WithMapType::G(g) => Ok(BaseTypes::G(g)),
-            WithMapType::H(h) => Ok(BaseTypes::H(h)),
-            WithMapType::I(i) => Ok(BaseTypes::I(i)),
-            WithMapType::J(_) | WithMapType::K(_) => Err(napi::Error::new(
-              napi::Status::GenericFailure,
-              "Nested collections or maps are not supported".to_string(),
-            )),
-          })
-        })
-      })
-      .collect::<napi::Result<Vec<BaseTypes>>>()
+  #[test]
+  fn ignores_if_in_names_and_literals() {
+    assert!(!ConditionalResult::is_conditional(
+      "INSERT INTO users (id, name) VALUES (1, 'if')"
+    ));
+    assert!(!ConditionalResult::is_conditional(
+      "UPDATE users SET \"if\" = 1, diff = 2 WHERE id = 1"
+    ));
   }
 }
@@ -1,9 +1,14 @@
+use chrono::{DateTime, Utc};
 use napi::bindgen_prelude::BigInt;
 use scylla::frame::response::result::CqlValue;
+use scylla::frame::value::CqlTimestamp;
+
+use super::cql_value_coercion::MAX_SAFE_INTEGER;
 
 use crate::types::{
-  decimal::Decimal, double::Double, duration::Duration, float::Float, list::List, map::Map,
-  set::Set, uuid::Uuid, varint::Varint,
+  blob::Blob, decimal::Decimal, double::Double, duration::Duration, float::Float, list::List,
+  local_date::LocalDate, local_time::LocalTime, map::Map, set::Set, tuple::Tuple, uuid::Uuid,
+  varint::Varint,
 };
 
 // Trait to abstract the conversion to CqlValue
@@ -12,9 +17,22 @@ pub trait ToCqlValue {
 }
 
 // Implement ToCqlValue for various types
-impl ToCqlValue for u32 {
+/// JS numbers are sent as the smallest type that holds them: `int`, then `bigint` for safe integers and `double`
+/// otherwise, `-0` included so that its sign is kept. They are converted to the actual type of the column when
+/// bound, see `coerce_cql_value`
+impl ToCqlValue for f64 {
   fn to_cql_value(&self) -> CqlValue {
-    CqlValue::Int(*self as i32)
+    if self.fract() != 0.0
+      || !self.is_finite()
+      || self.abs() > MAX_SAFE_INTEGER
+      || (*self == 0.0 && self.is_sign_negative())
+    {
+      CqlValue::Double(*self)
+    } else if (f64::from(i32::MIN)..=f64::from(i32::MAX)).contains(self) {
+      CqlValue::Int(*self as i32)
+    } else {
+      CqlValue::BigInt(*self as i64)
+    }
   }
 }
 
@@ -30,9 +48,26 @@ impl ToCqlValue for &Uuid {
   }
 }
 
+/// BigInts that do not fit in 64 bits are sent as `varint`
 impl ToCqlValue for BigInt {
   fn to_cql_value(&self) -> CqlValue {
-    CqlValue::BigInt(self.get_i64().0)
+    match self.get_i64() {
+      (value, true) => CqlValue::BigInt(value),
+      (_, false) => {
+        let bytes = self
+          .words
+          .iter()
+          .flat_map(|word| word.to_le_bytes())
+          .collect::<Vec<_>>();
+        let sign = if self.sign_bit {
+          num_bigint::Sign::Minus
+        } else {
+          num_bigint::Sign::Plus
+        };
+
+        CqlValue::Varint(num_bigint::BigInt::from_bytes_le(sign, &bytes).into())
+      }
+    }
   }
 }
 
@@ -84,19 +119,131 @@ impl ToCqlValue for &Set {
   }
 }
 
+impl ToCqlValue for &Tuple {
+  fn to_cql_value(&self) -> CqlValue {
+    CqlValue::Tuple((*self).into())
+  }
+}
+
 impl ToCqlValue for &Map {
   fn to_cql_value(&self) -> CqlValue {
     CqlValue::Map(self.inner.clone())
   }
 }
 
-// Helper function to convert u32 vector to u8 vector
-fn u32_vec_to_u8_vec(input: &[u32]) -> Vec<u8> {
-  input.iter().map(|&num| num as u8).collect()
+impl ToCqlValue for Blob {
+  // `CqlValue::Blob` owns its bytes, blobs bound directly to a `blob` column skip this copy in `serialize_value`
+  fn to_cql_value(&self) -> CqlValue {
+    CqlValue::Blob(self.as_bytes().to_vec())
+  }
+}
+
+impl ToCqlValue for DateTime<Utc> {
+  fn to_cql_value(&self) -> CqlValue {
+    CqlValue::Timestamp(CqlTimestamp(self.timestamp_millis()))
+  }
+}
+
+impl ToCqlValue for &LocalDate {
+  fn to_cql_value(&self) -> CqlValue {
+    CqlValue::Date((*self).into())
+  }
 }
 
-impl ToCqlValue for Vec<u32> {
+impl ToCqlValue for &LocalTime {
   fn to_cql_value(&self) -> CqlValue {
-    CqlValue::Blob(u32_vec_to_u8_vec(self))
+    CqlValue::Time((*self).into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn big_int(sign_bit: bool, words: &[u64]) -> BigInt {
+    BigInt {
+      sign_bit,
+      words: words.to_vec(),
+    }
+  }
+
+  fn varint(value: &str) -> CqlValue {
+    CqlValue::Varint(value.parse::<num_bigint::BigInt>().unwrap().into())
+  }
+
+  #[test]
+  fn numbers_are_sent_as_the_smallest_type() {
+    assert_eq!(42.0.to_cql_value(), CqlValue::Int(42));
+    assert_eq!((-42.0).to_cql_value(), CqlValue::Int(-42));
+    assert_eq!(f64::from(i32::MIN).to_cql_value(), CqlValue::Int(i32::MIN));
+    assert_eq!(f64::from(i32::MAX).to_cql_value(), CqlValue::Int(i32::MAX));
+    assert_eq!(
+      (f64::from(i32::MAX) + 1.0).to_cql_value(),
+      CqlValue::BigInt(i64::from(i32::MAX) + 1)
+    );
+    assert_eq!(
+      (f64::from(i32::MIN) - 1.0).to_cql_value(),
+      CqlValue::BigInt(i64::from(i32::MIN) - 1)
+    );
+    assert_eq!(
+      (-MAX_SAFE_INTEGER).to_cql_value(),
+      CqlValue::BigInt(-9_007_199_254_740_991)
+    );
+  }
+
+  #[test]
+  fn other_numbers_are_sent_as_doubles() {
+    assert_eq!(1.5.to_cql_value(), CqlValue::Double(1.5));
+    assert_eq!((-0.25).to_cql_value(), CqlValue::Double(-0.25));
+    // 2^53 is the first integer above `Number.MAX_SAFE_INTEGER`
+    assert_eq!(
+      9_007_199_254_740_992.0.to_cql_value(),
+      CqlValue::Double(9_007_199_254_740_992.0)
+    );
+    assert_eq!(
+      f64::INFINITY.to_cql_value(),
+      CqlValue::Double(f64::INFINITY)
+    );
+    assert_eq!(0.0.to_cql_value(), CqlValue::Int(0));
+    match (-0.0).to_cql_value() {
+      CqlValue::Double(zero) => assert!(zero == 0.0 && zero.is_sign_negative()),
+      value => panic!("-0 must be sent as a double, got {value:?}"),
+    }
+  }
+
+  #[test]
+  fn bigints_are_sent_as_bigint_when_they_fit() {
+    assert_eq!(big_int(false, &[42]).to_cql_value(), CqlValue::BigInt(42));
+    assert_eq!(big_int(true, &[42]).to_cql_value(), CqlValue::BigInt(-42));
+    assert_eq!(
+      big_int(false, &[i64::MAX as u64]).to_cql_value(),
+      CqlValue::BigInt(i64::MAX)
+    );
+    assert_eq!(
+      big_int(true, &[1 << 63]).to_cql_value(),
+      CqlValue::BigInt(i64::MIN)
+    );
+  }
+
+  #[test]
+  fn bigints_outside_64_bits_are_sent_as_varint() {
+    // i64::MAX + 1 and i64::MIN - 1
+    assert_eq!(
+      big_int(false, &[1 << 63]).to_cql_value(),
+      varint("9223372036854775808")
+    );
+    assert_eq!(
+      big_int(true, &[(1 << 63) + 1]).to_cql_value(),
+      varint("-9223372036854775809")
+    );
+    // 2^64 and -2^64
+    assert_eq!(
+      big_int(false, &[0, 1]).to_cql_value(),
+      varint("18446744073709551616")
+    );
+    assert_eq!(
+      big_int(true, &[0, 1]).to_cql_value(),
+      varint("-18446744073709551616")
+    );
   }
 }
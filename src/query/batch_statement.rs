@@ -4,6 +4,41 @@ use napi::Either;
 use scylla::batch::Batch;
 
 use super::{scylla_prepared_statement::PreparedStatement, scylla_query::Query};
+use crate::cluster::execution_profile::{
+  consistency::Consistency, serial_consistency::SerialConsistency,
+};
+
+/// Kind of batch, see the [CQL documentation](https://opensource.docs.scylladb.com/stable/cql/dml.html#batch-statement)
+#[napi]
+pub enum BatchType {
+  /// Guarantees that either all or none of the statements are applied, at the cost of writing the batch to
+  /// a batch log first
+  Logged,
+  /// Skips the batch log, cheaper when all the statements target the same partition
+  Unlogged,
+  /// Batch of counter updates, the only kind allowed to contain them
+  Counter,
+}
+
+impl From<BatchType> for scylla::batch::BatchType {
+  fn from(value: BatchType) -> Self {
+    match value {
+      BatchType::Logged => Self::Logged,
+      BatchType::Unlogged => Self::Unlogged,
+      BatchType::Counter => Self::Counter,
+    }
+  }
+}
+
+impl From<scylla::batch::BatchType> for BatchType {
+  fn from(value: scylla::batch::BatchType) -> Self {
+    match value {
+      scylla::batch::BatchType::Logged => Self::Logged,
+      scylla::batch::BatchType::Unlogged => Self::Unlogged,
+      scylla::batch::BatchType::Counter => Self::Counter,
+    }
+  }
+}
 
 /// Batch statements
 ///
@@ -35,13 +70,19 @@ impl Display for ScyllaBatchStatement {
 
 #[napi]
 impl ScyllaBatchStatement {
+  /// Creates an empty batch, logged unless another `batchType` is given
   #[napi(constructor)]
-  pub fn new() -> Self {
+  pub fn new(batch_type: Option<BatchType>) -> Self {
     Self {
-      batch: Default::default(),
+      batch: Batch::new(batch_type.unwrap_or(BatchType::Logged).into()),
     }
   }
 
+  #[napi]
+  pub fn get_type(&self) -> BatchType {
+    self.batch.get_type().into()
+  }
+
   /// Appends a statement to the batch.
   ///
   /// _Warning_
@@ -55,10 +96,68 @@ impl ScyllaBatchStatement {
         .append_statement(prepared_statement.prepared.clone()),
     }
   }
+
+  #[napi]
+  pub fn set_consistency(&mut self, consistency: Consistency) {
+    self.batch.set_consistency(consistency.into());
+  }
+
+  /// `null` when the consistency of the execution profile is used
+  #[napi]
+  pub fn get_consistency(&self) -> Option<Consistency> {
+    self.batch.get_consistency().map(Into::into)
+  }
+
+  /// Serial consistency of the conditional statements of the batch
+  #[napi]
+  pub fn set_serial_consistency(&mut self, serial_consistency: SerialConsistency) {
+    self
+      .batch
+      .set_serial_consistency(Some(serial_consistency.into()));
+  }
+
+  #[napi]
+  pub fn get_serial_consistency(&self) -> Option<SerialConsistency> {
+    self.batch.get_serial_consistency().map(Into::into)
+  }
+
+  /// Sets the default timestamp of the statements of the batch, in microseconds since the unix epoch.\
+  /// `null` lets the database assign the timestamp.
+  #[napi]
+  pub fn set_timestamp(&mut self, timestamp: Option<i64>) {
+    self.batch.set_timestamp(timestamp);
+  }
+
+  #[napi]
+  pub fn get_timestamp(&self) -> Option<i64> {
+    self.batch.get_timestamp()
+  }
+
+  /// Marks the batch as idempotent, which allows it to be retried or speculatively executed.\
+  /// Counter updates are not idempotent, counter batches must not be marked as such.
+  #[napi]
+  pub fn set_is_idempotent(&mut self, is_idempotent: bool) {
+    self.batch.set_is_idempotent(is_idempotent);
+  }
+
+  #[napi]
+  pub fn get_is_idempotent(&self) -> bool {
+    self.batch.get_is_idempotent()
+  }
+
+  #[napi]
+  pub fn set_tracing(&mut self, tracing: bool) {
+    self.batch.set_tracing(tracing);
+  }
+
+  #[napi]
+  pub fn get_tracing(&self) -> bool {
+    self.batch.get_tracing()
+  }
 }
 
 impl Default for ScyllaBatchStatement {
   fn default() -> Self {
-    Self::new()
+    Self::new(None)
   }
 }
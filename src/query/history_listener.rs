@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use scylla::history::HistoryCollector;
+
+/// Records what happened while executing the statements it is attached to: every attempt, the node it was
+/// sent to, its result and the retry decision taken after a failure.
+///
+/// # Example
+/// ```javascript
+/// const listener = new HistoryListener();
+/// query.setHistoryListener(listener);
+///
+/// await session.execute(query);
+/// console.log(listener.getHistory());
+/// ```
+#[napi]
+#[derive(Default)]
+pub struct HistoryListener {
+  pub(crate) collector: Arc<HistoryCollector>,
+}
+
+#[napi]
+impl HistoryListener {
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns the history collected so far, formatted as readable text
+  #[napi]
+  pub fn get_history(&self) -> String {
+    self.collector.clone_structured_history().to_string()
+  }
+
+  /// Discards the history collected so far
+  #[napi]
+  pub fn clear(&self) {
+    self.collector.take_collected();
+  }
+}
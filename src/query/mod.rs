@@ -1,3 +1,4 @@
 pub mod batch_statement;
+pub mod history_listener;
 pub mod scylla_prepared_statement;
 pub mod scylla_query;
@@ -1,18 +1,55 @@
+use std::time::Duration;
+
+use napi::bindgen_prelude::Buffer;
+use scylla::frame::response::result::ColumnSpec;
 use scylla::prepared_statement;
 
 use crate::cluster::execution_profile::{
-  consistency::Consistency, serial_consistency::SerialConsistency,
+  consistency::Consistency, retry_policy::RetryPolicySetting,
+  serial_consistency::SerialConsistency, ExecutionProfileHandle,
 };
+use crate::helpers::cql_value_coercion::cql_type_name;
+use crate::query::history_listener::HistoryListener;
 
 #[napi]
 pub struct PreparedStatement {
   pub(crate) prepared: prepared_statement::PreparedStatement,
+  // Kept to be returned by the getters, the driver only keeps the policy and the profile handle
+  retry_policy: Option<RetryPolicySetting>,
+  execution_profile: Option<ExecutionProfileHandle>,
+}
+
+/// Specification of a bind marker or of a result column of a prepared statement
+#[napi(object)]
+#[derive(Clone)]
+pub struct ColumnSpecification {
+  pub name: String,
+  pub keyspace: String,
+  pub table: String,
+  /// Type as written in CQL, such as `int` or `map<text, list<int>>`
+  #[napi(js_name = "type")]
+  pub typ: String,
+}
+
+impl From<&ColumnSpec> for ColumnSpecification {
+  fn from(spec: &ColumnSpec) -> Self {
+    ColumnSpecification {
+      name: spec.name.clone(),
+      keyspace: spec.table_spec.ks_name().to_string(),
+      table: spec.table_spec.table_name().to_string(),
+      typ: cql_type_name(&spec.typ),
+    }
+  }
 }
 
 #[napi]
 impl PreparedStatement {
   pub fn new(prepared: prepared_statement::PreparedStatement) -> Self {
-    Self { prepared }
+    Self {
+      prepared,
+      retry_policy: None,
+      execution_profile: None,
+    }
   }
 
   #[napi]
@@ -20,10 +57,188 @@ impl PreparedStatement {
     self.prepared.set_consistency(consistency.into());
   }
 
+  /// `null` when the consistency of the execution profile is used
+  #[napi]
+  pub fn get_consistency(&self) -> Option<Consistency> {
+    self.prepared.get_consistency().map(Into::into)
+  }
+
   #[napi]
   pub fn set_serial_consistency(&mut self, serial_consistency: SerialConsistency) {
     self
       .prepared
       .set_serial_consistency(Some(serial_consistency.into()));
   }
+
+  #[napi]
+  pub fn get_serial_consistency(&self) -> Option<SerialConsistency> {
+    self.prepared.get_serial_consistency().map(Into::into)
+  }
+
+  #[napi]
+  pub fn set_page_size(&mut self, page_size: i32) {
+    self.prepared.set_page_size(page_size);
+  }
+
+  #[napi]
+  pub fn get_page_size(&self) -> Option<i32> {
+    self.prepared.get_page_size()
+  }
+
+  /// Sets the request timeout in milliseconds, `null` to use the `requestTimeout` of the execution profile, which is in seconds
+  #[napi]
+  pub fn set_request_timeout(&mut self, timeout: Option<u32>) {
+    self
+      .prepared
+      .set_request_timeout(timeout.map(|timeout| Duration::from_millis(timeout.into())));
+  }
+
+  /// Request timeout in milliseconds, `null` when the `requestTimeout` of the execution profile (in seconds) is used
+  #[napi]
+  pub fn get_request_timeout(&self) -> Option<u32> {
+    self
+      .prepared
+      .get_request_timeout()
+      .map(|timeout| timeout.as_millis().try_into().unwrap_or(u32::MAX))
+  }
+
+  /// Marks the statement as idempotent, which allows it to be retried or speculatively executed
+  #[napi]
+  pub fn set_is_idempotent(&mut self, is_idempotent: bool) {
+    self.prepared.set_is_idempotent(is_idempotent);
+  }
+
+  #[napi]
+  pub fn get_is_idempotent(&self) -> bool {
+    self.prepared.get_is_idempotent()
+  }
+
+  #[napi]
+  pub fn set_tracing(&mut self, tracing: bool) {
+    self.prepared.set_tracing(tracing);
+  }
+
+  #[napi]
+  pub fn get_tracing(&self) -> bool {
+    self.prepared.get_tracing()
+  }
+
+  /// Sets the default timestamp of the statement, in microseconds since the unix epoch.\
+  /// `null` lets the database assign the timestamp.
+  #[napi]
+  pub fn set_timestamp(&mut self, timestamp: Option<i64>) {
+    self.prepared.set_timestamp(timestamp);
+  }
+
+  #[napi]
+  pub fn get_timestamp(&self) -> Option<i64> {
+    self.prepared.get_timestamp()
+  }
+
+  /// Sets the retry policy of the statement, `null` to use the one of the execution profile
+  #[napi]
+  pub fn set_retry_policy(&mut self, retry_policy: Option<RetryPolicySetting>) {
+    self
+      .prepared
+      .set_retry_policy(retry_policy.clone().map(RetryPolicySetting::into_policy));
+    self.retry_policy = retry_policy;
+  }
+
+  #[napi]
+  pub fn get_retry_policy(&self) -> Option<RetryPolicySetting> {
+    self.retry_policy.clone()
+  }
+
+  /// Sets the execution profile of the statement, `null` to use the session's default one
+  #[napi]
+  pub fn set_execution_profile(&mut self, execution_profile: Option<&ExecutionProfileHandle>) {
+    self.prepared.set_execution_profile_handle(
+      execution_profile.map(|execution_profile| execution_profile.handle.clone()),
+    );
+    self.execution_profile = execution_profile.cloned();
+  }
+
+  #[napi]
+  pub fn get_execution_profile(&self) -> Option<ExecutionProfileHandle> {
+    self.execution_profile.clone()
+  }
+
+  /// Records the history of every execution of the statement in `listener`
+  #[napi]
+  pub fn set_history_listener(&mut self, listener: &HistoryListener) {
+    self
+      .prepared
+      .set_history_listener(listener.collector.clone());
+  }
+
+  #[napi]
+  pub fn remove_history_listener(&mut self) {
+    self.prepared.remove_history_listener();
+  }
+
+  /// Keyspace of the table the statement operates on, `null` when the statement has no bind markers
+  #[napi]
+  pub fn get_keyspace(&self) -> Option<String> {
+    self.prepared.get_keyspace_name().map(ToString::to_string)
+  }
+
+  /// Table the statement operates on, `null` when the statement has no bind markers
+  #[napi]
+  pub fn get_table(&self) -> Option<String> {
+    self.prepared.get_table_name().map(ToString::to_string)
+  }
+
+  /// Text of the statement, as it was prepared
+  #[napi]
+  pub fn get_statement(&self) -> String {
+    self.prepared.get_statement().to_string()
+  }
+
+  /// Id assigned to the statement by the database
+  #[napi]
+  pub fn get_id(&self) -> Buffer {
+    self.prepared.get_id().to_vec().into()
+  }
+
+  /// Bind markers of the statement, in the order their values are expected
+  #[napi]
+  pub fn get_variables(&self) -> Vec<ColumnSpecification> {
+    self
+      .prepared
+      .get_variable_col_specs()
+      .iter()
+      .map(ColumnSpecification::from)
+      .collect()
+  }
+
+  /// Columns of the rows returned by the statement, empty for statements that do not return rows
+  #[napi]
+  pub fn get_result_columns(&self) -> Vec<ColumnSpecification> {
+    self
+      .prepared
+      .get_result_set_col_specs()
+      .iter()
+      .map(ColumnSpecification::from)
+      .collect()
+  }
+
+  /// Indexes of the bind markers that make up the partition key, in the order of the partition key columns.\
+  /// Empty when the partition key is not fully bound, in which case the statement is not routed to its replicas.
+  #[napi]
+  pub fn get_partition_key_indexes(&self) -> Vec<u32> {
+    let mut pk_indexes = self.prepared.get_variable_pk_indexes().to_vec();
+    pk_indexes.sort_unstable_by_key(|pk_index| pk_index.sequence);
+
+    pk_indexes
+      .into_iter()
+      .map(|pk_index| u32::from(pk_index.index))
+      .collect()
+  }
+
+  /// Whether the statement is known to be a lightweight transaction (`IF NOT EXISTS`, `IF ...`).\
+  /// Only ScyllaDB reports it, it is always `false` on Cassandra.
+  #[napi]
+  pub fn is_lwt(&self) -> bool {
+    self.prepared.is_confirmed_lwt()
+  }
 }
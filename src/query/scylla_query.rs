@@ -1,13 +1,24 @@
 use std::fmt::Display;
+use std::time::Duration;
 
 use crate::cluster::execution_profile::{
-  consistency::Consistency, serial_consistency::SerialConsistency,
+  consistency::Consistency, retry_policy::RetryPolicySetting,
+  serial_consistency::SerialConsistency, ExecutionProfileHandle,
 };
+use crate::query::history_listener::HistoryListener;
 use scylla::query;
 
+/// Unprepared statement, sent as text along with its parameters.
+///
+/// Its options mirror the ones of `PreparedStatement`, except for `getKeyspace`: the driver only learns the
+/// keyspace of a statement from the database when preparing it, so prepare the statement with
+/// `session.prepare` to read it.
 #[napi]
 pub struct Query {
   pub(crate) query: query::Query,
+  // Kept to be returned by the getters, the driver only keeps the policy and the profile handle
+  retry_policy: Option<RetryPolicySetting>,
+  execution_profile: Option<ExecutionProfileHandle>,
 }
 
 impl Display for Query {
@@ -22,6 +33,8 @@ impl Query {
   pub fn new(query: String) -> Self {
     Self {
       query: query::Query::new(query),
+      retry_policy: None,
+      execution_profile: None,
     }
   }
 
@@ -30,6 +43,12 @@ impl Query {
     self.query.set_consistency(consistency.into());
   }
 
+  /// `null` when the consistency of the execution profile is used
+  #[napi]
+  pub fn get_consistency(&self) -> Option<Consistency> {
+    self.query.get_consistency().map(Into::into)
+  }
+
   #[napi]
   pub fn set_serial_consistency(&mut self, serial_consistency: SerialConsistency) {
     self
@@ -37,8 +56,107 @@ impl Query {
       .set_serial_consistency(Some(serial_consistency.into()));
   }
 
+  #[napi]
+  pub fn get_serial_consistency(&self) -> Option<SerialConsistency> {
+    self.query.get_serial_consistency().map(Into::into)
+  }
+
   #[napi]
   pub fn set_page_size(&mut self, page_size: i32) {
     self.query.set_page_size(page_size);
   }
+
+  #[napi]
+  pub fn get_page_size(&self) -> Option<i32> {
+    self.query.get_page_size()
+  }
+
+  /// Sets the request timeout in milliseconds, `null` to use the `requestTimeout` of the execution profile, which is in seconds
+  #[napi]
+  pub fn set_request_timeout(&mut self, timeout: Option<u32>) {
+    self
+      .query
+      .set_request_timeout(timeout.map(|timeout| Duration::from_millis(timeout.into())));
+  }
+
+  /// Request timeout in milliseconds, `null` when the `requestTimeout` of the execution profile (in seconds) is used
+  #[napi]
+  pub fn get_request_timeout(&self) -> Option<u32> {
+    self
+      .query
+      .get_request_timeout()
+      .map(|timeout| timeout.as_millis().try_into().unwrap_or(u32::MAX))
+  }
+
+  /// Marks the statement as idempotent, which allows it to be retried or speculatively executed
+  #[napi]
+  pub fn set_is_idempotent(&mut self, is_idempotent: bool) {
+    self.query.set_is_idempotent(is_idempotent);
+  }
+
+  #[napi]
+  pub fn get_is_idempotent(&self) -> bool {
+    self.query.get_is_idempotent()
+  }
+
+  #[napi]
+  pub fn set_tracing(&mut self, tracing: bool) {
+    self.query.set_tracing(tracing);
+  }
+
+  #[napi]
+  pub fn get_tracing(&self) -> bool {
+    self.query.get_tracing()
+  }
+
+  /// Sets the default timestamp of the statement, in microseconds since the unix epoch.\
+  /// `null` lets the database assign the timestamp.
+  #[napi]
+  pub fn set_timestamp(&mut self, timestamp: Option<i64>) {
+    self.query.set_timestamp(timestamp);
+  }
+
+  #[napi]
+  pub fn get_timestamp(&self) -> Option<i64> {
+    self.query.get_timestamp()
+  }
+
+  /// Sets the retry policy of the statement, `null` to use the one of the execution profile
+  #[napi]
+  pub fn set_retry_policy(&mut self, retry_policy: Option<RetryPolicySetting>) {
+    self
+      .query
+      .set_retry_policy(retry_policy.clone().map(RetryPolicySetting::into_policy));
+    self.retry_policy = retry_policy;
+  }
+
+  #[napi]
+  pub fn get_retry_policy(&self) -> Option<RetryPolicySetting> {
+    self.retry_policy.clone()
+  }
+
+  /// Sets the execution profile of the statement, `null` to use the session's default one
+  #[napi]
+  pub fn set_execution_profile(&mut self, execution_profile: Option<&ExecutionProfileHandle>) {
+    self.query.set_execution_profile_handle(
+      execution_profile.map(|execution_profile| execution_profile.handle.clone()),
+    );
+    self.execution_profile = execution_profile.cloned();
+  }
+
+  #[napi]
+  pub fn get_execution_profile(&self) -> Option<ExecutionProfileHandle> {
+    self.execution_profile.clone()
+  }
+
+  /// Records the history of every execution of the statement in `listener`
+  #[napi]
+  pub fn set_history_listener(&mut self, listener: &HistoryListener) {
+    self.query.set_history_listener(listener.collector.clone());
+  }
+
+  #[napi]
+  pub fn remove_history_listener(&mut self) {
+    self.query.remove_history_listener();
+  }
 }
@@ -2,15 +2,21 @@ use std::sync::Arc;
 
 use crate::error::pipe_error_from_string;
 
+use super::prepared_cache::PreparedStatementCache;
+
 #[napi]
 pub struct Metrics {
   metrics: Arc<scylla::Metrics>,
+  prepared_cache: Arc<PreparedStatementCache>,
 }
 
 #[napi]
 impl Metrics {
-  pub fn new(metrics: Arc<scylla::Metrics>) -> Self {
-    Self { metrics }
+  pub fn new(metrics: Arc<scylla::Metrics>, prepared_cache: Arc<PreparedStatementCache>) -> Self {
+    Self {
+      metrics,
+      prepared_cache,
+    }
   }
 
   /// Returns counter for nonpaged queries
@@ -58,4 +64,22 @@ impl Metrics {
       .get_latency_percentile_ms(percentile.clamp(0.0, 100.0))
       .map_err(pipe_error_from_string)
   }
+
+  /// Returns counter for statements found in the prepared statement cache
+  #[napi]
+  pub fn get_prepared_cache_hits(&self) -> u64 {
+    self.prepared_cache.hits()
+  }
+
+  /// Returns counter for statements that had to be prepared because they were not in the cache
+  #[napi]
+  pub fn get_prepared_cache_misses(&self) -> u64 {
+    self.prepared_cache.misses()
+  }
+
+  /// Returns the number of statements currently in the prepared statement cache
+  #[napi]
+  pub fn get_prepared_cache_size(&self) -> u32 {
+    self.prepared_cache.size() as u32
+  }
 }
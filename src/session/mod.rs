@@ -1,3 +1,5 @@
 pub mod metrics;
+pub mod prepared_cache;
+pub mod row_iterator;
 pub mod scylla_session;
 pub mod topology;
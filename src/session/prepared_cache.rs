@@ -0,0 +1,83 @@
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use lru::LruCache;
+use scylla::prepared_statement::PreparedStatement;
+
+/// Number of statements kept when `preparedStatementCacheSize` is not set
+pub const DEFAULT_PREPARED_CACHE_SIZE: u32 = 512;
+
+/// Statements prepared by the session on behalf of the caller, keyed by their text.
+///
+/// Only the least recently used statements are evicted once the cache is full. The lock is never held across
+/// an await, preparing a statement happens outside of it.
+pub struct PreparedStatementCache {
+  statements: Option<Mutex<LruCache<String, PreparedStatement>>>,
+  hits: AtomicU64,
+  misses: AtomicU64,
+}
+
+impl PreparedStatementCache {
+  /// A `size` of 0 disables the cache, every lookup is then a miss
+  pub fn new(size: u32) -> Self {
+    Self {
+      statements: NonZeroUsize::new(size as usize).map(|size| Mutex::new(LruCache::new(size))),
+      hits: AtomicU64::new(0),
+      misses: AtomicU64::new(0),
+    }
+  }
+
+  pub fn get(&self, statement: &str) -> Option<PreparedStatement> {
+    let prepared = self.statements.as_ref().and_then(|statements| {
+      statements
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(statement)
+        .cloned()
+    });
+
+    match prepared {
+      Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+      None => self.misses.fetch_add(1, Ordering::Relaxed),
+    };
+
+    prepared
+  }
+
+  pub fn insert(&self, statement: String, prepared: PreparedStatement) {
+    if let Some(statements) = &self.statements {
+      statements
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .put(statement, prepared);
+    }
+  }
+
+  /// Drops every statement, they are prepared again on their next use
+  pub fn clear(&self) {
+    if let Some(statements) = &self.statements {
+      statements
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
+    }
+  }
+
+  pub fn hits(&self) -> u64 {
+    self.hits.load(Ordering::Relaxed)
+  }
+
+  pub fn misses(&self) -> u64 {
+    self.misses.load(Ordering::Relaxed)
+  }
+
+  pub fn size(&self) -> usize {
+    self.statements.as_ref().map_or(0, |statements| {
+      statements
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .len()
+    })
+  }
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use futures::{FutureExt, StreamExt};
+use scylla::transport::errors::QueryError;
+use scylla::transport::iterator::RowIterator;
+use tokio::sync::Mutex;
+
+use crate::error::query_error;
+use crate::helpers::query_results::{JSQueryRow, QueryResult};
+
+/// Iterator over the rows of a multi-page result.
+///
+/// Pages are fetched lazily: the next page is only requested from the database once the rows of the
+/// current one were consumed, so iterating a large table never holds more than a couple of pages in memory.
+///
+/// The iterator implements the async iterator protocol, so it can be consumed with `for await`.
+///
+/// # Example
+/// ```javascript
+/// const rows = await session.executeIter("SELECT * FROM users");
+///
+/// for await (const row of rows) {
+///   console.log(row);
+/// }
+/// ```
+#[napi]
+pub struct ScyllaRowIterator {
+  inner: Arc<Mutex<RowIteratorState>>,
+}
+
+struct RowIteratorState {
+  iterator: RowIterator,
+  // Error met after some rows of a page were read, thrown by the next call
+  pending_error: Option<QueryError>,
+}
+
+#[napi]
+impl ScyllaRowIterator {
+  pub fn new(iterator: RowIterator) -> Self {
+    Self {
+      inner: Arc::new(Mutex::new(RowIteratorState {
+        iterator,
+        pending_error: None,
+      })),
+    }
+  }
+
+  /// Returns the rows of the current page, fetching the next page if the current one is exhausted.\
+  /// Rows of the following page are included when it was already received. Resolves to `null` once every
+  /// row was consumed.
+  #[napi]
+  pub async fn next_page(&self) -> napi::Result<Option<Vec<JSQueryRow>>> {
+    let mut state = self.inner.lock().await;
+    let RowIteratorState {
+      iterator,
+      pending_error,
+    } = &mut *state;
+
+    if let Some(e) = pending_error.take() {
+      return Err(query_error(
+        e,
+        "Something went wrong fetching the next page.",
+      ));
+    }
+
+    let mut rows = match iterator.next().await {
+      Some(Ok(row)) => vec![row],
+      Some(Err(e)) => {
+        return Err(query_error(
+          e,
+          "Something went wrong fetching the next page.",
+        ));
+      }
+      None => return Ok(None),
+    };
+
+    // Takes the rows that are already there, without waiting for another page
+    while let Some(Some(next)) = iterator.next().now_or_never() {
+      match next {
+        Ok(row) => rows.push(row),
+        Err(e) => {
+          *pending_error = Some(e);
+          break;
+        }
+      }
+    }
+
+    let column_specs = iterator.get_column_specs();
+    rows
+      .into_iter()
+      .map(|row| QueryResult::parse_row(row, column_specs))
+      .collect::<napi::Result<Vec<_>>>()
+      .map(Some)
+  }
+}
@@ -1,185 +1,619 @@
-use crate::helpers::cql_value_bridge::ParameterWithMapType;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cluster::execution_profile::{
+  consistency::Consistency, serial_consistency::SerialConsistency, ExecutionProfileHandle,
+};
+use crate::error::{query_error, ErrorKind, ScyllaError};
+use crate::helpers::cql_value_bridge::JSQueryParameters;
 use crate::helpers::query_parameter::QueryParameter;
-use crate::helpers::query_results::{JSQueryResult, QueryResult};
+use crate::helpers::query_results::{
+  ConditionalResult, JSQueryResult, JSQueryRow, PagedResult, QueryResult,
+};
 use crate::query::batch_statement::ScyllaBatchStatement;
 use crate::query::scylla_prepared_statement::PreparedStatement;
 use crate::query::scylla_query::Query;
-use crate::types::tracing::TracingReturn;
+use crate::types::tracing::{TracingInfo, TracingReturn};
 use crate::types::uuid::Uuid;
-use napi::bindgen_prelude::Either3;
-use napi::Either;
+use napi::bindgen_prelude::{Buffer, Either3, FromNapiValue};
+use futures::stream::{FuturesUnordered, StreamExt};
+use napi::{sys, Either, JsObject};
+use scylla::batch::BatchStatement;
+use scylla::serialize::row::SerializedValues;
 use scylla::statement::query::Query as ScyllaQuery;
+use scylla::transport::errors::QueryError;
 
 use super::metrics;
+use super::prepared_cache::PreparedStatementCache;
+use super::row_iterator::ScyllaRowIterator;
 use super::topology::ScyllaClusterData;
 
+/// Options of a single request, they take precedence over the ones set on the statement and the execution profile
 #[napi(object)]
+#[derive(Clone, Default)]
 pub struct QueryOptions {
+  /// Prepares the statement before executing it. Prepared statements are cached by the session, so a given
+  /// statement is only prepared once
   pub prepare: Option<bool>,
+  pub consistency: Option<Consistency>,
+  pub serial_consistency: Option<SerialConsistency>,
+  /// Request timeout in milliseconds, overriding the `requestTimeout` of the execution profile, which is in seconds
+  pub timeout: Option<u32>,
+  /// Number of rows per page, ignored by batches
+  pub page_size: Option<i32>,
+  /// Default timestamp of the request, in microseconds since the unix epoch
+  pub timestamp: Option<i64>,
+  /// Marks the request as idempotent, which allows it to be retried or speculatively executed
+  pub is_idempotent: Option<bool>,
+  pub tracing: Option<bool>,
+  /// Execution profile used for this request instead of the session's default one
+  pub execution_profile: Option<ExecutionProfileHandle>,
+}
+
+macro_rules! apply_common_options {
+  ($options:expr, $statement:expr) => {
+    if let Some(consistency) = $options.consistency {
+      $statement.set_consistency(consistency.into());
+    }
+    if let Some(serial_consistency) = $options.serial_consistency {
+      $statement.set_serial_consistency(Some(serial_consistency.into()));
+    }
+    if let Some(timestamp) = $options.timestamp {
+      $statement.set_timestamp(Some(timestamp));
+    }
+    if let Some(is_idempotent) = $options.is_idempotent {
+      $statement.set_is_idempotent(is_idempotent);
+    }
+    if let Some(tracing) = $options.tracing {
+      $statement.set_tracing(tracing);
+    }
+    if let Some(execution_profile) = &$options.execution_profile {
+      $statement.set_execution_profile_handle(Some(execution_profile.handle.clone()));
+    }
+  };
+}
+
+impl QueryOptions {
+  fn should_prepare(&self) -> bool {
+    self.prepare.unwrap_or(false)
+  }
+
+  fn request_timeout(&self) -> Option<Duration> {
+    self
+      .timeout
+      .map(|timeout| Duration::from_millis(timeout.into()))
+  }
+
+  pub(crate) fn apply_to_query(&self, query: &mut ScyllaQuery) {
+    apply_common_options!(self, query);
+    if let Some(page_size) = self.page_size {
+      query.set_page_size(page_size);
+    }
+    if let Some(timeout) = self.request_timeout() {
+      query.set_request_timeout(Some(timeout));
+    }
+  }
+
+  pub(crate) fn apply_to_prepared(
+    &self,
+    prepared: &mut scylla::prepared_statement::PreparedStatement,
+  ) {
+    apply_common_options!(self, prepared);
+    if let Some(page_size) = self.page_size {
+      prepared.set_page_size(page_size);
+    }
+    if let Some(timeout) = self.request_timeout() {
+      prepared.set_request_timeout(Some(timeout));
+    }
+  }
+
+  /// Batches have no request timeout of their own, the timeout is enforced in `ScyllaSession::batch` instead
+  pub(crate) fn apply_to_batch(&self, batch: &mut scylla::batch::Batch) {
+    apply_common_options!(self, batch);
+  }
+}
+
+/// Options of `executePaged`, every option of `QueryOptions` along with the paging state.\
+/// `pageSize` defaults to the page size of the statement, or to 5000 rows.
+#[derive(Default)]
+pub struct PagedQueryOptions {
+  options: QueryOptions,
+  /// Paging state returned by a previous call, omit it to fetch the first page
+  paging_state: Option<Buffer>,
+}
+
+impl FromNapiValue for PagedQueryOptions {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+    let options = QueryOptions::from_napi_value(env, napi_val)?;
+    let object = JsObject::from_napi_value(env, napi_val)?;
+
+    Ok(Self {
+      options,
+      paging_state: object.get("pagingState")?,
+    })
+  }
+}
+
+const DEFAULT_PAGE_SIZE: i32 = 5000;
+
+/// Statement of a request, ready to be sent
+enum Statement {
+  Query(ScyllaQuery),
+  Prepared(scylla::prepared_statement::PreparedStatement),
+}
+
+impl Statement {
+  fn contents(&self) -> &str {
+    match self {
+      Self::Query(query) => &query.contents,
+      Self::Prepared(prepared) => prepared.get_statement(),
+    }
+  }
+
+  fn page_size(&self) -> Option<i32> {
+    match self {
+      Self::Query(query) => query.get_page_size(),
+      Self::Prepared(prepared) => prepared.get_page_size(),
+    }
+  }
+
+  fn set_page_size(&mut self, page_size: i32) {
+    match self {
+      Self::Query(query) => query.set_page_size(page_size),
+      Self::Prepared(prepared) => prepared.set_page_size(page_size),
+    }
+  }
+
+  fn set_tracing(&mut self, tracing: bool) {
+    match self {
+      Self::Query(query) => query.set_tracing(tracing),
+      Self::Prepared(prepared) => prepared.set_tracing(tracing),
+    }
+  }
+}
+
+#[napi(object)]
+pub struct ConcurrentOptions {
+  /// Maximum number of requests in flight at the same time, defaults to 100
+  pub concurrency: Option<u32>,
+  /// Stops sending the remaining rows after the first failure, defaults to `false`.\
+  /// Requests already in flight are still awaited and counted.
+  pub stop_on_error: Option<bool>,
+}
+
+const DEFAULT_CONCURRENCY: u32 = 100;
+
+/// Failure of a single row of `executeConcurrent`
+#[napi(object, object_from_js = false)]
+pub struct ConcurrentError {
+  /// Index of the row in the given rows
+  pub index: u32,
+  /// Name of the error class the failure would have been thrown as, such as `WriteTimeoutError`
+  pub name: String,
+  pub message: String,
+}
+
+#[napi(object)]
+pub struct PartitionBatchOptions {
+  /// Maximum number of rows in a single batch, larger partitions are split into several batches. Defaults to 100
+  pub max_batch_size: Option<u32>,
+  /// Maximum number of batches in flight at the same time, defaults to 100
+  pub concurrency: Option<u32>,
+}
+
+const DEFAULT_MAX_BATCH_SIZE: u32 = 100;
+
+/// Failure of a batch of `batchByPartition`, or of a single row whose partition key could not be computed
+#[napi(object, object_from_js = false)]
+pub struct PartitionBatchError {
+  /// Indexes of the rows of the batch in the given array
+  pub rows: Vec<u32>,
+  /// Name of the error class the failure would have been thrown as, such as `WriteTimeoutError`
+  pub name: String,
+  pub message: String,
+}
+
+#[napi(object, object_from_js = false)]
+pub struct PartitionBatchResult {
+  /// Number of batches sent
+  pub batches: u32,
+  /// Number of rows written
+  pub succeeded: u32,
+  /// Number of rows that were not written
+  pub failed: u32,
+  /// Failures ordered by the index of their first row
+  pub errors: Vec<PartitionBatchError>,
+}
+
+#[napi(object, object_from_js = false)]
+pub struct ConcurrentResult {
+  pub succeeded: u32,
+  pub failed: u32,
+  /// Number of rows that were never sent because of `stopOnError`. Rows left in an iterator are not read:
+  /// only the chunk already read ahead is counted
+  pub unsent: u32,
+  /// Failures ordered by the index of their row
+  pub errors: Vec<ConcurrentError>,
 }
 
 #[napi]
 pub struct ScyllaSession {
   session: scylla::Session,
+  prepared_cache: Arc<PreparedStatementCache>,
 }
 
 #[napi]
 impl ScyllaSession {
-  pub fn new(session: scylla::Session) -> Self {
-    Self { session }
+  pub fn new(session: scylla::Session, prepared_cache_size: u32) -> Self {
+    Self {
+      session,
+      prepared_cache: Arc::new(PreparedStatementCache::new(prepared_cache_size)),
+    }
   }
 
   #[napi]
   pub fn metrics(&self) -> metrics::Metrics {
-    metrics::Metrics::new(self.session.get_metrics())
+    metrics::Metrics::new(self.session.get_metrics(), self.prepared_cache.clone())
   }
 
   #[napi]
-  pub async fn get_cluster_data(&self) -> ScyllaClusterData {
+  pub async fn get_cluster_data(&self) -> napi::Result<ScyllaClusterData> {
     self
       .session
       .refresh_metadata()
       .await
-      .expect("Failed to refresh metadata");
+      .map_err(|e| query_error(e, "Failed to refresh metadata"))?;
 
     let cluster_data = self.session.get_cluster_data();
-    cluster_data.into()
+    Ok(cluster_data.into())
   }
 
   #[napi]
   pub async fn execute_with_tracing(
     &self,
     query: Either3<String, &Query, &PreparedStatement>,
-    parameters: Option<Vec<ParameterWithMapType<'_>>>,
+    parameters: Option<JSQueryParameters<'_>>,
     options: Option<QueryOptions>,
   ) -> napi::Result<TracingReturn> {
-    let values = QueryParameter::parser(parameters.clone()).ok_or_else(|| {
-      napi::Error::new(
-        napi::Status::InvalidArg,
-        format!(
-          "Something went wrong with your query parameters. {:?}",
-          parameters
-        ),
-      )
-    })?;
-
-    let should_prepare = options.map_or(false, |options| options.prepare.unwrap_or(false));
-
-    match query {
-      Either3::A(ref query_str) if should_prepare => {
-        let mut prepared = self.session.prepare(query_str.clone()).await.map_err(|e| {
-          napi::Error::new(
-            napi::Status::InvalidArg,
-            format!(
-              "Something went wrong preparing your statement. - [{}]\n{}",
-              query_str, e
-            ),
-          )
-        })?;
-        prepared.set_tracing(true);
-        self.execute_prepared(&prepared, values, query_str).await
-      }
-      Either3::A(query_str) => {
-        let mut query = ScyllaQuery::new(query_str);
-        query.set_tracing(true);
-        self.execute_query(Either::B(query), values).await
-      }
-      Either3::B(query_ref) => {
-        let mut query = query_ref.query.clone();
-        query.set_tracing(true);
-
-        self.execute_query(Either::B(query), values).await
-      }
-      Either3::C(prepared_ref) => {
-        let mut prepared = prepared_ref.prepared.clone();
-        prepared.set_tracing(true);
+    let options = options.unwrap_or_default();
+    let (mut statement, values) = self.build_request(query, parameters, &options).await?;
+    statement.set_tracing(true);
 
-        self
-          .execute_prepared(&prepared, values, prepared_ref.prepared.get_statement())
-          .await
-      }
-    }
+    let (result, tracing) = self.execute_statement(statement, values).await?;
+    Ok(TracingReturn::from([
+      ("result".to_string(), Either::A(result)),
+      ("tracing".to_string(), Either::B(tracing.into())),
+    ]))
   }
 
   /// Sends a query to the database and receives a response.\
-  /// Returns only a single page of results, to receive multiple pages use (TODO: Not implemented yet)
+  /// Returns only a single page of results, to receive multiple pages use `executeIter`
   ///
   /// This is the easiest way to make a query, but performance is worse than that of prepared queries.
   ///
   /// It is discouraged to use this method with non-empty values argument. In such case, query first needs to be prepared (on a single connection), so
   /// driver will perform 2 round trips instead of 1. Please use `PreparedStatement` object or `{ prepared: true }` option instead.
   ///
+  /// Options such as the consistency or the timeout can be set for a single call, without building a `Query`:
+  /// ```javascript
+  /// await session.execute("SELECT * FROM users", [], { consistency: Consistency.One, timeout: 2000 });
+  /// ```
+  ///
+  /// Parameters are bound in order when given as an array, or by name when given as an object, in which
+  /// case each key is matched against the name of a bind marker. Like CQL identifiers, names are case
+  /// insensitive unless the marker is quoted (`:"userId"`):
+  /// ```javascript
+  /// await session.execute("SELECT * FROM users WHERE id = :userId", { userId }, { prepare: true });
+  /// ```
+  ///
+  /// A wrong number of parameters, or a value that cannot be converted to the type of its bind marker, is
+  /// rejected with a `SerializationError` naming the parameter, the expected CQL type and the received JS type.
+  ///
   /// # Notes
   ///
   /// ## UDT
-  /// Order of fields in the object must match the order of fields as defined in the UDT. The
-  /// driver does not check it by itself, so incorrect data will be written if the order is
-  /// wrong.
+  /// Fields of an object bound to a UDT are matched by name, in any order. Missing fields are sent as
+  /// `null`, and a field the type does not have is rejected with a `SerializationError`.
   #[napi]
   pub async fn execute(
     &self,
     query: Either3<String, &Query, &PreparedStatement>,
-    parameters: Option<Vec<ParameterWithMapType<'_>>>,
+    parameters: Option<JSQueryParameters<'_>>,
     options: Option<QueryOptions>,
   ) -> JSQueryResult {
-    let values = QueryParameter::parser(parameters.clone()).ok_or_else(|| {
-      napi::Error::new(
-        napi::Status::InvalidArg,
-        format!(
-          "Something went wrong with your query parameters. {:?}",
-          parameters
-        ),
+    let options = options.unwrap_or_default();
+    let (statement, values) = self.build_request(query, parameters, &options).await?;
+
+    let (result, _tracing) = self.execute_statement(statement, values).await?;
+    Ok(result)
+  }
+
+  /// Sends a query to the database and returns an iterator over all the rows of the result.\
+  /// Unlike `execute`, the result is not limited to a single page: pages are fetched on demand, as the
+  /// iterator is consumed.
+  ///
+  /// The page size can be configured through `Query.setPageSize`, it defaults to 5000 rows.
+  ///
+  /// # Example
+  /// ```javascript
+  /// const rows = await session.executeIter("SELECT * FROM users WHERE age > ?", [18], { prepare: true });
+  ///
+  /// for await (const row of rows) {
+  ///   console.log(row);
+  /// }
+  /// ```
+  #[napi]
+  pub async fn execute_iter(
+    &self,
+    query: Either3<String, &Query, &PreparedStatement>,
+    parameters: Option<JSQueryParameters<'_>>,
+    options: Option<QueryOptions>,
+  ) -> napi::Result<ScyllaRowIterator> {
+    let options = options.unwrap_or_default();
+    let (statement, values) = self.build_request(query, parameters, &options).await?;
+    let query_str = statement.contents().to_string();
+
+    let iterator = match statement {
+      Statement::Query(query) => self.session.query_iter(query, values).await,
+      Statement::Prepared(prepared) => self.session.execute_iter(prepared, values).await,
+    }
+    .map_err(|e| {
+      query_error(
+        e,
+        format!("Something went wrong with your query. - [{}]", query_str),
+      )
+    })?;
+
+    Ok(ScyllaRowIterator::new(iterator))
+  }
+
+  /// Sends a query to the database and receives a single page of the result, along with the paging state
+  /// needed to fetch the next one.
+  ///
+  /// The paging state is an opaque `Buffer`, it can be stored or sent to a client and handed back later to
+  /// resume reading from where the previous page stopped. It is absent once the last page was fetched.
+  ///
+  /// Every option of `execute` applies as well, the page size defaults to the one of the statement, or to
+  /// 5000 rows.
+  ///
+  /// # Example
+  /// ```javascript
+  /// let pagingState = undefined;
+  ///
+  /// do {
+  ///   const page = await session.executePaged("SELECT * FROM users", [], { pageSize: 100, pagingState });
+  ///   console.log(page.rows);
+  ///   pagingState = page.pagingState;
+  /// } while (pagingState);
+  /// ```
+  #[napi]
+  pub async fn execute_paged(
+    &self,
+    query: Either3<String, &Query, &PreparedStatement>,
+    parameters: Option<JSQueryParameters<'_>>,
+    options: Option<PagedQueryOptions>,
+  ) -> napi::Result<PagedResult> {
+    let PagedQueryOptions {
+      options,
+      paging_state,
+    } = options.unwrap_or_default();
+    let paging_state =
+      paging_state.map(|paging_state| bytes::Bytes::copy_from_slice(&paging_state));
+
+    let (mut statement, values) = self.build_request(query, parameters, &options).await?;
+    if statement.page_size().is_none() {
+      statement.set_page_size(DEFAULT_PAGE_SIZE);
+    }
+    let query_str = statement.contents().to_string();
+
+    let query_result = match statement {
+      Statement::Query(query) => self.session.query_paged(query, values, paging_state).await,
+      Statement::Prepared(prepared) => {
+        self
+          .session
+          .execute_paged(&prepared, values, paging_state)
+          .await
+      }
+    }
+    .map_err(|e| {
+      query_error(
+        e,
+        format!("Something went wrong with your query. - [{}]", query_str),
       )
     })?;
 
-    let should_prepare = options.map_or(false, |options| options.prepare.unwrap_or(false));
+    QueryResult::paged_parser(query_result)
+  }
 
-    let result = match query {
-      Either3::A(ref query_str) if should_prepare => {
-        let prepared = self.session.prepare(query_str.clone()).await.map_err(|e| {
-          napi::Error::new(
-            napi::Status::InvalidArg,
+  // Helper method to send batches, which have no request timeout of their own. The context of the error is
+  // only built when the batch fails
+  async fn send_batch(
+    &self,
+    batch: &scylla::batch::Batch,
+    values: Vec<QueryParameter<'_>>,
+    timeout: Option<Duration>,
+    context: impl FnOnce() -> String,
+  ) -> Result<scylla::QueryResult, ScyllaError> {
+    let result = match timeout {
+      Some(timeout) => match tokio::time::timeout(timeout, self.session.batch(batch, values)).await
+      {
+        Ok(result) => result,
+        Err(_) => {
+          return Err(ScyllaError::new(
+            ErrorKind::RequestTimeoutError,
             format!(
-              "Something went wrong preparing your statement. - [{}]\n{}",
-              query_str, e
+              "Request timeout: batch did not complete within {timeout:?} - {}",
+              context()
+            ),
+          ));
+        }
+      },
+      None => self.session.batch(batch, values).await,
+    };
+
+    result.map_err(|e| {
+      ScyllaError::from_query_error(
+        &e,
+        format!("Something went wrong with your batch. - {}", context()),
+      )
+    })
+  }
+
+  // Helper method of `batch_by_partition`, returns the indexes of the rows of the batch along with its result
+  async fn execute_partition_batch(
+    &self,
+    prepared: &scylla::prepared_statement::PreparedStatement,
+    rows: Vec<(usize, QueryParameter<'_>)>,
+    options: &QueryOptions,
+  ) -> (Vec<u32>, Result<(), ScyllaError>) {
+    let mut batch = scylla::batch::Batch::new(scylla::batch::BatchType::Unlogged);
+    for _ in &rows {
+      batch.append_statement(prepared.clone());
+    }
+    options.apply_to_batch(&mut batch);
+
+    let (indexes, values): (Vec<_>, Vec<_>) = rows
+      .into_iter()
+      .map(|(index, values)| (index as u32, values))
+      .unzip();
+    let result = self
+      .send_batch(&batch, values, options.request_timeout(), || {
+        format!("[{}] - rows {indexes:?}", prepared.get_statement())
+      })
+      .await
+      .map(|_| ());
+
+    (indexes, result)
+  }
+
+  // Helper method of `batch_by_partition`, sends the batches of a partition in order
+  async fn execute_partition(
+    &self,
+    prepared: &scylla::prepared_statement::PreparedStatement,
+    chunks: Vec<Vec<(usize, QueryParameter<'_>)>>,
+    options: &QueryOptions,
+  ) -> Vec<(Vec<u32>, Result<(), ScyllaError>)> {
+    let mut results = Vec::with_capacity(chunks.len());
+    for chunk in chunks {
+      results.push(self.execute_partition_batch(prepared, chunk, options).await);
+    }
+
+    results
+  }
+
+  /// Indexes of the bind markers of the primary key columns, `None` when the statement does not bind all of
+  /// them or the table is unknown
+  fn primary_key_indexes(
+    &self,
+    prepared: &scylla::prepared_statement::PreparedStatement,
+  ) -> Option<Vec<usize>> {
+    let cluster_data = self.session.get_cluster_data();
+    let table = cluster_data
+      .get_keyspace_info()
+      .get(prepared.get_keyspace_name()?)?
+      .tables
+      .get(prepared.get_table_name()?)?;
+    let columns = prepared.get_variable_col_specs();
+
+    table
+      .partition_key
+      .iter()
+      .chain(&table.clustering_key)
+      .map(|name| columns.iter().position(|column| &column.name == name))
+      .collect()
+  }
+
+  // Helper method of `execute_concurrent`, returns the index of the row along with its result
+  async fn execute_row(
+    &self,
+    prepared: &scylla::prepared_statement::PreparedStatement,
+    index: usize,
+    parameters: JSQueryParameters<'_>,
+  ) -> (usize, Result<scylla::QueryResult, QueryError>) {
+    let values = QueryParameter {
+      parameters: Some(parameters),
+    };
+
+    (index, self.session.execute(prepared, values).await)
+  }
+
+  // Helper method parsing the parameters of a request and building the statement to send, with the options of
+  // the request applied. Strings are prepared first when the `prepare` option is set
+  async fn build_request<'a>(
+    &self,
+    query: Either3<String, &Query, &PreparedStatement>,
+    parameters: Option<JSQueryParameters<'a>>,
+    options: &QueryOptions,
+  ) -> napi::Result<(Statement, QueryParameter<'a>)> {
+    let values = QueryParameter::parser(parameters);
+
+    let statement = match query {
+      Either3::A(query_str) if options.should_prepare() => {
+        let mut prepared = self.prepare_cached(&query_str).await.map_err(|e| {
+          query_error(
+            e,
+            format!(
+              "Something went wrong preparing your statement. - [{}]",
+              query_str
             ),
           )
         })?;
-        self.execute_prepared(&prepared, values, query_str).await
+        options.apply_to_prepared(&mut prepared);
+        Statement::Prepared(prepared)
+      }
+      Either3::A(query_str) => {
+        let mut query = ScyllaQuery::new(query_str);
+        options.apply_to_query(&mut query);
+        Statement::Query(query)
       }
-      Either3::A(query_str) => self.execute_query(Either::A(query_str), values).await,
       Either3::B(query_ref) => {
-        self
-          .execute_query(Either::B(query_ref.query.clone()), values)
-          .await
+        let mut query = query_ref.query.clone();
+        options.apply_to_query(&mut query);
+        Statement::Query(query)
       }
       Either3::C(prepared_ref) => {
+        let mut prepared = prepared_ref.prepared.clone();
+        options.apply_to_prepared(&mut prepared);
+        Statement::Prepared(prepared)
+      }
+    };
+
+    Ok((statement, values))
+  }
+
+  // Helper method sending a statement built by `build_request`
+  async fn execute_statement(
+    &self,
+    statement: Statement,
+    values: QueryParameter<'_>,
+  ) -> napi::Result<(Vec<JSQueryRow>, Option<TracingInfo>)> {
+    match statement {
+      Statement::Query(query) => self.execute_query(query, values).await,
+      Statement::Prepared(prepared) => {
         self
-          .execute_prepared(
-            &prepared_ref.prepared,
-            values,
-            prepared_ref.prepared.get_statement(),
-          )
+          .execute_prepared(&prepared, values, prepared.get_statement())
           .await
       }
     }
-    .map_err(|e| {
-      napi::Error::new(
-        napi::Status::InvalidArg,
-        format!("Something went wrong with your query. - \n{}", e), // TODO: handle different queries here
-      )
-    })?
-    .get("result")
-    .cloned()
-    .ok_or(napi::Error::new(
-      napi::Status::InvalidArg,
-      r#"Something went wrong with your query."#.to_string(), // TODO: handle different queries here
-    ))?;
-
-    match result {
-      Either::A(results) => Ok(results),
-      Either::B(_tracing) => unreachable!(),
+  }
+
+  /// Prepares the statement, or reuses it when it was already prepared by a previous call
+  async fn prepare_cached(
+    &self,
+    query: &str,
+  ) -> Result<scylla::prepared_statement::PreparedStatement, QueryError> {
+    if let Some(prepared) = self.prepared_cache.get(query) {
+      return Ok(prepared);
     }
+
+    let prepared = self.session.prepare(query).await?;
+    self
+      .prepared_cache
+      .insert(query.to_string(), prepared.clone());
+
+    Ok(prepared)
   }
 
   // Helper method to handle prepared statements
@@ -188,30 +622,27 @@ impl ScyllaSession {
     prepared: &scylla::prepared_statement::PreparedStatement,
     values: QueryParameter<'_>,
     query: &str,
-  ) -> napi::Result<TracingReturn> {
+  ) -> napi::Result<(Vec<JSQueryRow>, Option<TracingInfo>)> {
     let query_result = self.session.execute(prepared, values).await.map_err(|e| {
-      napi::Error::new(
-        napi::Status::InvalidArg,
+      query_error(
+        e,
         format!(
-          "Something went wrong with your prepared statement. - [{}]\n{}",
-          query, e
+          "Something went wrong with your prepared statement. - [{}]",
+          query
         ),
       )
     })?;
 
     let tracing = if let Some(tracing_id) = query_result.tracing_id {
-      Some(crate::types::tracing::TracingInfo::from(
+      Some(TracingInfo::from(
         self
           .session
           .get_tracing_info(&tracing_id)
           .await
           .map_err(|e| {
-            napi::Error::new(
-              napi::Status::InvalidArg,
-              format!(
-                "Something went wrong with your tracing info. - [{}]\n{}",
-                query, e
-              ),
+            query_error(
+              e,
+              format!("Something went wrong with your tracing info. - [{}]", query),
             )
           })?,
       ))
@@ -219,54 +650,41 @@ impl ScyllaSession {
       None
     };
 
-    let result = QueryResult::parser(query_result)?;
-
-    Ok(TracingReturn::from([
-      ("result".to_string(), Either::A(result)),
-      ("tracing".to_string(), Either::B(tracing.into())),
-    ]))
+    Ok((QueryResult::parser(query_result)?, tracing))
   }
 
   // Helper method to handle direct queries
   async fn execute_query(
     &self,
-    query: Either<String, scylla::query::Query>,
+    query: scylla::query::Query,
     values: QueryParameter<'_>,
-  ) -> napi::Result<TracingReturn> {
-    let query_result = match &query {
-      Either::A(query_str) => self.session.query(query_str.clone(), values).await,
-      Either::B(query_ref) => self.session.query(query_ref.clone(), values).await,
-    }
-    .map_err(|e| {
-      let query_str = match query.clone() {
-        Either::A(query_str) => query_str,
-        Either::B(query_ref) => query_ref.contents.clone(),
-      };
-      napi::Error::new(
-        napi::Status::InvalidArg,
-        format!(
-          "Something went wrong with your query. - [{}]\n{}",
-          query_str, e
-        ),
-      )
-    })?;
+  ) -> napi::Result<(Vec<JSQueryRow>, Option<TracingInfo>)> {
+    let query_result = self
+      .session
+      .query(query.clone(), values)
+      .await
+      .map_err(|e| {
+        query_error(
+          e,
+          format!(
+            "Something went wrong with your query. - [{}]",
+            query.contents
+          ),
+        )
+      })?;
 
     let tracing_info = if let Some(tracing_id) = query_result.tracing_id {
-      Some(crate::types::tracing::TracingInfo::from(
+      Some(TracingInfo::from(
         self
           .session
           .get_tracing_info(&tracing_id)
           .await
           .map_err(|e| {
-            napi::Error::new(
-              napi::Status::InvalidArg,
+            query_error(
+              e,
               format!(
-                "Something went wrong with your tracing info. - [{}]\n{}",
-                match query {
-                  Either::A(query_str) => query_str,
-                  Either::B(query_ref) => query_ref.contents.clone(),
-                },
-                e
+                "Something went wrong with your tracing info. - [{}]",
+                query.contents
               ),
             )
           })?,
@@ -275,13 +693,7 @@ impl ScyllaSession {
       None
     };
 
-    Ok(TracingReturn::from([
-      (
-        "result".to_string(),
-        Either::A(QueryResult::parser(query_result)?),
-      ),
-      ("tracing".to_string(), Either::B(tracing_info.into())),
-    ]))
+    Ok((QueryResult::parser(query_result)?, tracing_info))
   }
 
   #[allow(clippy::type_complexity)]
@@ -289,33 +701,302 @@ impl ScyllaSession {
   pub async fn query(
     &self,
     scylla_query: &Query,
-    parameters: Option<Vec<ParameterWithMapType<'_>>>,
+    parameters: Option<JSQueryParameters<'_>>,
+    options: Option<QueryOptions>,
   ) -> JSQueryResult {
-    let values = QueryParameter::parser(parameters.clone()).ok_or(napi::Error::new(
-      napi::Status::InvalidArg,
-      format!("Something went wrong with your query parameters. {parameters:?}"),
-    ))?;
+    let values = QueryParameter::parser(parameters.clone());
 
-    let query_result = self
-      .session
-      .query(scylla_query.query.clone(), values)
-      .await
-      .map_err(|e| {
-        napi::Error::new(
-          napi::Status::InvalidArg,
-          format!("Something went wrong with your query. - [{scylla_query}] - {parameters:?}\n{e}"),
-        )
-      })?;
+    let mut query = scylla_query.query.clone();
+    if let Some(options) = options {
+      options.apply_to_query(&mut query);
+    }
+
+    let query_result = self.session.query(query, values).await.map_err(|e| {
+      query_error(
+        e,
+        format!("Something went wrong with your query. - [{scylla_query}] - {parameters:?}"),
+      )
+    })?;
 
     QueryResult::parser(query_result)
   }
 
+  /// Executes the same statement once for each row of parameters, keeping up to `concurrency` requests in
+  /// flight. Failures do not reject the promise, they are counted and returned along with the index of their row.
+  ///
+  /// `rows` can be any iterable or async iterable, such as a generator reading a file. Rows are read in
+  /// chunks of `concurrency` rows, so they are never all held in memory: a chunk is sent once the previous
+  /// one completed, while the next one is being read. This is done in `scripts/fix-files.mjs`, the native
+  /// method receives the chunks.
+  ///
+  /// # Example
+  /// ```javascript
+  /// const insert = await session.prepare("INSERT INTO users (id, name) VALUES (?, ?)");
+  /// const { succeeded, failed, errors } = await session.executeConcurrent(insert, rows, { concurrency: 256 });
+  /// ```
+  #[napi(
+    ts_args_type = "statement: string | PreparedStatement, rows: Iterable<JSQueryParameters> | AsyncIterable<JSQueryParameters>, concurrentOptions?: ConcurrentOptions | undefined | null, options?: QueryOptions | undefined | null"
+  )]
+  pub async fn execute_concurrent(
+    &self,
+    statement: Either<String, &PreparedStatement>,
+    rows: Vec<JSQueryParameters<'_>>,
+    concurrent_options: Option<ConcurrentOptions>,
+    options: Option<QueryOptions>,
+  ) -> napi::Result<ConcurrentResult> {
+    let mut prepared = match statement {
+      Either::A(query) => self.prepare_cached(&query).await.map_err(|e| {
+        query_error(
+          e,
+          format!("Something went wrong preparing your statement. - [{query}]"),
+        )
+      })?,
+      Either::B(prepared) => prepared.prepared.clone(),
+    };
+    options.unwrap_or_default().apply_to_prepared(&mut prepared);
+
+    let concurrency = concurrent_options
+      .as_ref()
+      .and_then(|options| options.concurrency)
+      .unwrap_or(DEFAULT_CONCURRENCY)
+      .max(1) as usize;
+    let stop_on_error = concurrent_options
+      .as_ref()
+      .and_then(|options| options.stop_on_error)
+      .unwrap_or(false);
+
+    let mut rows = rows.into_iter().enumerate();
+    let mut in_flight = FuturesUnordered::new();
+    let mut succeeded = 0;
+    let mut errors = vec![];
+    let mut stopped = false;
+
+    loop {
+      while !stopped && in_flight.len() < concurrency {
+        match rows.next() {
+          Some((index, parameters)) => {
+            in_flight.push(self.execute_row(&prepared, index, parameters));
+          }
+          None => break,
+        }
+      }
+
+      let Some((index, result)) = in_flight.next().await else {
+        break;
+      };
+      match result {
+        Ok(_) => succeeded += 1,
+        Err(e) => {
+          let error = ScyllaError::from_query_error(
+            &e,
+            format!(
+              "Something went wrong with the row {index}. - [{}]",
+              prepared.get_statement()
+            ),
+          );
+          errors.push(ConcurrentError {
+            index: index as u32,
+            name: format!("{:?}", error.kind()),
+            message: error.message().to_string(),
+          });
+
+          stopped |= stop_on_error;
+        }
+      }
+    }
+    errors.sort_unstable_by_key(|error| error.index);
+
+    Ok(ConcurrentResult {
+      succeeded,
+      failed: errors.len() as u32,
+      unsent: rows.len() as u32,
+      errors,
+    })
+  }
+
+  /// Writes the rows with the given statement, grouping the rows of a same partition in unlogged batches.
+  ///
+  /// Each batch only targets a single partition, so it is sent straight to one of its replicas and does not
+  /// burden a coordinator with writes to other partitions. The statement must bind every column of the
+  /// partition key.
+  ///
+  /// The rows of a batch share the same write timestamp, so rows writing the same primary key are put in
+  /// different batches, and the batches of a partition are sent one after the other: the last row of the
+  /// input wins, as if the rows were written one by one. This does not hold when `timestamp` is set in
+  /// `options`, every batch then has the same timestamp.
+  ///
+  /// # Example
+  /// ```javascript
+  /// const insert = await session.prepare("INSERT INTO events (device, at, value) VALUES (?, ?, ?)");
+  /// const { batches, failed } = await session.batchByPartition(insert, rows, { maxBatchSize: 50 });
+  /// ```
+  #[napi]
+  pub async fn batch_by_partition(
+    &self,
+    statement: Either<String, &PreparedStatement>,
+    rows: Vec<JSQueryParameters<'_>>,
+    batch_options: Option<PartitionBatchOptions>,
+    options: Option<QueryOptions>,
+  ) -> napi::Result<PartitionBatchResult> {
+    let prepared = match statement {
+      Either::A(query) => self.prepare_cached(&query).await.map_err(|e| {
+        query_error(
+          e,
+          format!("Something went wrong preparing your statement. - [{query}]"),
+        )
+      })?,
+      Either::B(prepared) => prepared.prepared.clone(),
+    };
+    if !prepared.is_token_aware() {
+      return Err(
+        ScyllaError::new(
+          ErrorKind::InvalidQueryError,
+          format!(
+            "The statement does not bind the whole partition key, its rows cannot be grouped by partition - [{}]",
+            prepared.get_statement()
+          ),
+        )
+        .into(),
+      );
+    }
+
+    let max_batch_size = batch_options
+      .as_ref()
+      .and_then(|options| options.max_batch_size)
+      .unwrap_or(DEFAULT_MAX_BATCH_SIZE)
+      .max(1) as usize;
+    let concurrency = batch_options
+      .as_ref()
+      .and_then(|options| options.concurrency)
+      .unwrap_or(DEFAULT_CONCURRENCY)
+      .max(1) as usize;
+    let options = options.unwrap_or_default();
+
+    let mut errors = vec![];
+    let mut partitions: Vec<Vec<(usize, QueryParameter<'_>)>> = vec![];
+    let mut partition_of_key = HashMap::new();
+    for (index, parameters) in rows.into_iter().enumerate() {
+      let values = QueryParameter {
+        parameters: Some(parameters),
+      };
+
+      match prepared.compute_partition_key(&values) {
+        Ok(key) => {
+          let partition = *partition_of_key.entry(key).or_insert_with(|| {
+            partitions.push(vec![]);
+            partitions.len() - 1
+          });
+          partitions[partition].push((index, values));
+        }
+        Err(e) => errors.push(PartitionBatchError {
+          rows: vec![index as u32],
+          name: format!("{:?}", ErrorKind::SerializationError),
+          message: format!("Failed to compute the partition key of the row {index}\n{e}"),
+        }),
+      }
+    }
+
+    let key_indexes = self.primary_key_indexes(&prepared);
+    let partitions = partitions
+      .into_iter()
+      .map(|rows| {
+        let rows = rows
+          .into_iter()
+          .map(|(index, values)| {
+            let key = key_indexes
+              .as_ref()
+              .and_then(|key_indexes| primary_key(&prepared, &values, key_indexes));
+            ((index, values), key)
+          })
+          .collect();
+        split_partition(rows, max_batch_size)
+      })
+      .collect::<Vec<_>>();
+    let batches = partitions.iter().map(Vec::len).sum::<usize>() as u32;
+
+    let mut partitions = partitions.into_iter();
+    let mut in_flight = FuturesUnordered::new();
+    let mut succeeded = 0;
+
+    loop {
+      while in_flight.len() < concurrency {
+        match partitions.next() {
+          Some(chunks) => in_flight.push(self.execute_partition(&prepared, chunks, &options)),
+          None => break,
+        }
+      }
+
+      let Some(results) = in_flight.next().await else {
+        break;
+      };
+      for (rows, result) in results {
+        match result {
+          Ok(()) => succeeded += rows.len() as u32,
+          Err(error) => errors.push(PartitionBatchError {
+            rows,
+            name: format!("{:?}", error.kind()),
+            message: error.message().to_string(),
+          }),
+        }
+      }
+    }
+
+    errors.sort_unstable_by_key(|error| error.rows.first().copied());
+
+    Ok(PartitionBatchResult {
+      batches,
+      succeeded,
+      failed: errors.iter().map(|error| error.rows.len() as u32).sum(),
+      errors,
+    })
+  }
+
+  /// Executes a conditional statement (`INSERT ... IF NOT EXISTS`, `UPDATE ... IF ...`) and tells whether it
+  /// was applied, along with the existing values when it was not.\
+  /// Statements without an `IF` condition are rejected before being sent.
+  ///
+  /// # Example
+  /// ```javascript
+  /// const { applied, existing } = await session.executeConditional(
+  ///   "INSERT INTO users (id, name) VALUES (?, ?) IF NOT EXISTS",
+  ///   [id, "Alice"],
+  ///   { prepare: true },
+  /// );
+  /// if (!applied) console.log(`Already taken by ${existing?.name}`);
+  /// ```
+  #[napi]
+  pub async fn execute_conditional(
+    &self,
+    query: Either3<String, &Query, &PreparedStatement>,
+    parameters: Option<JSQueryParameters<'_>>,
+    options: Option<QueryOptions>,
+  ) -> napi::Result<ConditionalResult> {
+    let (is_conditional, statement) = match &query {
+      Either3::A(query) => (ConditionalResult::is_conditional(query), query.as_str()),
+      Either3::B(query) => (
+        ConditionalResult::is_conditional(&query.query.contents),
+        query.query.contents.as_str(),
+      ),
+      Either3::C(prepared) => {
+        let statement = prepared.prepared.get_statement();
+        (
+          prepared.prepared.is_confirmed_lwt() || ConditionalResult::is_conditional(statement),
+          statement,
+        )
+      }
+    };
+    ConditionalResult::ensure_conditional(is_conditional, statement)?;
+
+    let rows = self.execute(query, parameters, options).await?;
+    Ok(ConditionalResult::from_rows(rows))
+  }
+
   #[napi]
   pub async fn prepare(&self, query: String) -> napi::Result<PreparedStatement> {
     let prepared = self.session.prepare(query.clone()).await.map_err(|e| {
-      napi::Error::new(
-        napi::Status::InvalidArg,
-        format!("Something went wrong with your prepared statement. - [{query}]\n{e}"),
+      query_error(
+        e,
+        format!("Something went wrong with your prepared statement. - [{query}]"),
       )
     })?;
 
@@ -333,6 +1014,7 @@ impl ScyllaSession {
   /// # Arguments
   /// * `batch` - Batch to be performed
   /// * `values` - List of values for each query, it's the easiest to use an array of arrays
+  /// * `options` - Options of the request, `prepare` and `pageSize` do not apply to batches
   ///
   /// # Example
   /// ```javascript
@@ -362,32 +1044,68 @@ impl ScyllaSession {
   pub async fn batch(
     &self,
     batch: &ScyllaBatchStatement,
-    parameters: Vec<Option<Vec<ParameterWithMapType<'_>>>>,
+    parameters: Vec<Option<JSQueryParameters<'_>>>,
+    options: Option<QueryOptions>,
   ) -> JSQueryResult {
+    let statements = batch.batch.statements.len();
+    if parameters.len() != statements {
+      return Err(
+        ScyllaError::new(
+          ErrorKind::SerializationError,
+          format!(
+            "The batch has {statements} statements but parameters were given for {} - [{batch}]",
+            parameters.len()
+          ),
+        )
+        .into(),
+      );
+    }
+
     let values = parameters
       .iter()
-      .map(|params| {
-        QueryParameter::parser(params.clone()).ok_or(napi::Error::new(
-          napi::Status::InvalidArg,
-          format!("Something went wrong with your batch parameters. {parameters:?}"),
-        ))
-      })
-      .collect::<napi::Result<Vec<_>>>()?;
+      .cloned()
+      .map(QueryParameter::parser)
+      .collect::<Vec<_>>();
+
+    let options = options.unwrap_or_default();
+    let mut scylla_batch = batch.batch.clone();
+    options.apply_to_batch(&mut scylla_batch);
 
     let query_result = self
-      .session
-      .batch(&batch.batch, values)
-      .await
-      .map_err(|e| {
-        napi::Error::new(
-          napi::Status::InvalidArg,
-          format!("Something went wrong with your batch. - [{batch}] - {parameters:?}\n{e}"),
-        )
-      })?;
+      .send_batch(&scylla_batch, values, options.request_timeout(), || {
+        format!("[{batch}] - {parameters:?}")
+      })
+      .await?;
 
     QueryResult::parser(query_result)
   }
 
+  /// Performs a batch of conditional statements, which are applied only if all of their conditions hold.\
+  /// When the batch is not applied, `existing` holds the values of the first row returned by the database.\
+  /// Batches without any conditional statement are rejected before being sent.
+  #[napi]
+  pub async fn batch_conditional(
+    &self,
+    batch: &ScyllaBatchStatement,
+    parameters: Vec<Option<JSQueryParameters<'_>>>,
+    options: Option<QueryOptions>,
+  ) -> napi::Result<ConditionalResult> {
+    let is_conditional = batch
+      .batch
+      .statements
+      .iter()
+      .any(|statement| match statement {
+        BatchStatement::Query(query) => ConditionalResult::is_conditional(&query.contents),
+        BatchStatement::PreparedStatement(prepared) => {
+          prepared.is_confirmed_lwt() || ConditionalResult::is_conditional(prepared.get_statement())
+        }
+      });
+    ConditionalResult::ensure_conditional(is_conditional, batch)?;
+
+    let rows = self.batch(batch, parameters, options).await?;
+    Ok(ConditionalResult::from_rows(rows))
+  }
+
   /// Sends `USE <keyspace_name>` request on all connections\
   /// This allows to write `SELECT * FROM table` instead of `SELECT * FROM keyspace.table`\
   ///
@@ -436,12 +1154,15 @@ impl ScyllaSession {
       .use_keyspace(keyspace_name.clone(), case_sensitive.unwrap_or(false))
       .await
       .map_err(|e| {
-        napi::Error::new(
-          napi::Status::InvalidArg,
-          format!("Something went wrong with your keyspace. - [{keyspace_name}]\n{e}"),
+        query_error(
+          e,
+          format!("Something went wrong with your keyspace. - [{keyspace_name}]"),
         )
       })?;
 
+    // Statements prepared before may refer to tables of the previous keyspace
+    self.prepared_cache.clear();
+
     Ok(())
   }
 
@@ -476,12 +1197,7 @@ impl ScyllaSession {
         .session
         .await_schema_agreement()
         .await
-        .map_err(|e| {
-          napi::Error::new(
-            napi::Status::GenericFailure,
-            format!("Something went wrong with your schema agreement. - {e}"),
-          )
-        })?
+        .map_err(|e| query_error(e, "Something went wrong with your schema agreement."))?
         .into(),
     )
   }
@@ -493,13 +1209,95 @@ impl ScyllaSession {
         .session
         .check_schema_agreement()
         .await
-        .map_err(|e| {
-          napi::Error::new(
-            napi::Status::GenericFailure,
-            format!("Something went wrong with your schema agreement. - {e}"),
-          )
-        })?
+        .map_err(|e| query_error(e, "Something went wrong with your schema agreement."))?
         .is_some(),
     )
   }
 }
+
+/// Serialized values of the primary key of a row, `None` when they cannot be serialized
+fn primary_key(
+  prepared: &scylla::prepared_statement::PreparedStatement,
+  values: &QueryParameter,
+  key_indexes: &[usize],
+) -> Option<Vec<Option<Vec<u8>>>> {
+  let (values, ()) = SerializedValues::from_closure(|writer| {
+    values.write_row(prepared.get_variable_col_specs(), writer)
+  })
+  .ok()?;
+  let values = values.iter().collect::<Vec<_>>();
+
+  key_indexes
+    .iter()
+    .map(|&index| Some(values.get(index)?.as_value().map(<[u8]>::to_vec)))
+    .collect()
+}
+
+/// Splits the rows of a partition into batches of at most `max_batch_size` rows, starting a new batch when a
+/// row has the same primary key as a row of the current one. Rows without a known key get a batch of their own
+fn split_partition<T, K: Eq + Hash>(
+  rows: Vec<(T, Option<K>)>,
+  max_batch_size: usize,
+) -> Vec<Vec<T>> {
+  let mut batches = vec![];
+  let mut batch = vec![];
+  let mut keys = HashSet::new();
+  let mut has_unknown_key = false;
+
+  for (row, key) in rows {
+    let conflicts = has_unknown_key
+      || match &key {
+        Some(key) => keys.contains(key),
+        None => !batch.is_empty(),
+      };
+    if conflicts || batch.len() == max_batch_size {
+      batches.push(std::mem::take(&mut batch));
+      keys.clear();
+      has_unknown_key = false;
+    }
+
+    match key {
+      Some(key) => {
+        keys.insert(key);
+      }
+      None => has_unknown_key = true,
+    }
+    batch.push(row);
+  }
+  if !batch.is_empty() {
+    batches.push(batch);
+  }
+
+  batches
+}
+
+#[cfg(test)]
+mod tests {
+  use super::split_partition;
+
+  #[test]
+  fn splits_partitions_by_size() {
+    let rows = (0..5).map(|row| (row, Some(row))).collect();
+    assert_eq!(
+      split_partition(rows, 2),
+      vec![vec![0, 1], vec![2, 3], vec![4]]
+    );
+  }
+
+  #[test]
+  fn puts_duplicate_keys_in_separate_batches() {
+    let rows = vec![
+      (0, Some("a")),
+      (1, Some("b")),
+      (2, Some("a")),
+      (3, Some("c")),
+    ];
+    assert_eq!(split_partition(rows, 10), vec![vec![0, 1], vec![2, 3]]);
+  }
+
+  #[test]
+  fn isolates_rows_without_key() {
+    let rows = vec![(0, Some("a")), (1, None), (2, Some("b"))];
+    assert_eq!(split_partition(rows, 10), vec![vec![0], vec![1], vec![2]]);
+  }
+}
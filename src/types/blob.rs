@@ -0,0 +1,135 @@
+use std::fmt::Debug;
+use std::ptr;
+
+use bytes::Bytes;
+use napi::bindgen_prelude::{FromNapiValue, TypeName, TypedArrayType, Uint8Array, ValidateNapiValue};
+use napi::{check_status, sys, ValueType};
+
+/// Binary parameter, bound to `blob` columns.
+///
+/// Accepts a `Buffer`, an `Uint8Array` or an `ArrayBuffer`. The bytes are copied once, in one go, when the
+/// parameter is received: the request is sent after the call returns, so it cannot borrow memory that JS may
+/// change, transfer or collect in the meantime. A blob bound to a `blob` column is written from that copy,
+/// while a blob inside a `List`, `Set`, `Map`, `Tuple` or object is copied again into the collection.
+#[derive(Clone)]
+pub struct Blob {
+  bytes: Bytes,
+}
+
+impl Blob {
+  pub(crate) fn as_bytes(&self) -> &[u8] {
+    &self.bytes
+  }
+
+  fn copy_from(array: Uint8Array) -> Self {
+    Self {
+      bytes: Bytes::copy_from_slice(&array),
+    }
+  }
+}
+
+impl Debug for Blob {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Blob")
+      .field("length", &self.bytes.len())
+      .finish()
+  }
+}
+
+impl TypeName for Blob {
+  fn type_name() -> &'static str {
+    "Blob"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for Blob {
+  unsafe fn validate(
+    env: sys::napi_env,
+    napi_val: sys::napi_value,
+  ) -> napi::Result<sys::napi_value> {
+    if unsafe { is_array_buffer(env, napi_val)? } {
+      return Ok(ptr::null_mut());
+    }
+
+    let mut typed_array_type = 0;
+    let mut is_typed_array = false;
+    check_status!(
+      unsafe { sys::napi_is_typedarray(env, napi_val, &mut is_typed_array) },
+      "Failed to check if value is a typed array"
+    )?;
+    if is_typed_array {
+      check_status!(
+        unsafe {
+          sys::napi_get_typedarray_info(
+            env,
+            napi_val,
+            &mut typed_array_type,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+          )
+        },
+        "Failed to get typed array info"
+      )?;
+    }
+
+    if !is_typed_array || typed_array_type != TypedArrayType::Uint8 as i32 {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "Expected a Buffer, an Uint8Array or an ArrayBuffer",
+      ));
+    }
+
+    Ok(ptr::null_mut())
+  }
+}
+
+impl FromNapiValue for Blob {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+    if !unsafe { is_array_buffer(env, napi_val)? } {
+      return Ok(Self::copy_from(unsafe {
+        Uint8Array::from_napi_value(env, napi_val)?
+      }));
+    }
+
+    // Wraps the `ArrayBuffer` in a view over the same memory
+    let mut length = 0;
+    check_status!(
+      unsafe { sys::napi_get_arraybuffer_info(env, napi_val, ptr::null_mut(), &mut length) },
+      "Failed to get ArrayBuffer info"
+    )?;
+    let mut view = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_typedarray(
+          env,
+          TypedArrayType::Uint8 as i32,
+          length,
+          napi_val,
+          0,
+          &mut view,
+        )
+      },
+      "Failed to create a view over the ArrayBuffer"
+    )?;
+
+    Ok(Self::copy_from(unsafe {
+      Uint8Array::from_napi_value(env, view)?
+    }))
+  }
+}
+
+unsafe fn is_array_buffer(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<bool> {
+  let mut is_array_buffer = false;
+  check_status!(
+    unsafe { sys::napi_is_arraybuffer(env, napi_val, &mut is_array_buffer) },
+    "Failed to check if value is an ArrayBuffer"
+  )?;
+
+  Ok(is_array_buffer)
+}
@@ -0,0 +1,143 @@
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDate};
+use napi::Result;
+use scylla::frame::value::CqlDate;
+
+/// Number of days between -5877641-06-23, the first CQL date, and the unix epoch
+const EPOCH_OFFSET: i64 = 1 << 31;
+
+/// Native CQL `date` representation, a date without a time zone.
+///
+/// Unlike `Date`, it represents every date a `date` column can hold, counted in days since the unix epoch.
+#[napi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalDate {
+  pub(crate) date: CqlDate,
+}
+
+impl From<CqlDate> for LocalDate {
+  fn from(date: CqlDate) -> Self {
+    Self { date }
+  }
+}
+
+impl From<&LocalDate> for CqlDate {
+  fn from(value: &LocalDate) -> Self {
+    value.date
+  }
+}
+
+impl LocalDate {
+  fn naive_date(&self) -> Option<NaiveDate> {
+    self.date.try_into().ok()
+  }
+}
+
+#[napi]
+impl LocalDate {
+  /// Creates a date from its year, month (1-12) and day (1-31).
+  #[napi(constructor)]
+  pub fn new(year: i32, month: u32, day: u32) -> Result<Self> {
+    NaiveDate::from_ymd_opt(year, month, day)
+      .map(|date| Self { date: date.into() })
+      .ok_or_else(|| {
+        napi::Error::new(
+          napi::Status::InvalidArg,
+          format!("Invalid date: {year}-{month}-{day}"),
+        )
+      })
+  }
+
+  /// Parses a date in the `yyyy-mm-dd` format.
+  #[napi]
+  pub fn from_string(str: String) -> Result<LocalDate> {
+    let date = NaiveDate::from_str(&str).map_err(|e| {
+      napi::Error::new(
+        napi::Status::InvalidArg,
+        format!("Failed to parse date: {}", e),
+      )
+    })?;
+
+    Ok(Self { date: date.into() })
+  }
+
+  /// Creates a date from the number of days since the unix epoch, negative for dates before it.
+  #[napi]
+  pub fn from_days_since_epoch(days: i64) -> Result<LocalDate> {
+    days
+      .checked_add(EPOCH_OFFSET)
+      .and_then(|days| u32::try_from(days).ok())
+      .map(|days| Self {
+        date: CqlDate(days),
+      })
+      .ok_or_else(|| {
+        napi::Error::new(
+          napi::Status::InvalidArg,
+          format!("Date out of range: {days} days since epoch"),
+        )
+      })
+  }
+
+  #[napi(getter)]
+  pub fn days_since_epoch(&self) -> i64 {
+    i64::from(self.date.0) - EPOCH_OFFSET
+  }
+
+  /// `null` when the date is too far from the epoch to be represented in the gregorian calendar.
+  #[napi(getter)]
+  pub fn year(&self) -> Option<i32> {
+    self.naive_date().map(|date| date.year())
+  }
+
+  #[napi(getter)]
+  pub fn month(&self) -> Option<u32> {
+    self.naive_date().map(|date| date.month())
+  }
+
+  #[napi(getter)]
+  pub fn day(&self) -> Option<u32> {
+    self.naive_date().map(|date| date.day())
+  }
+
+  /// Returns the date in the `yyyy-mm-dd` format, or the number of days since the epoch when it is out of the
+  /// range of the gregorian calendar.
+  #[napi]
+  #[allow(clippy::inherent_to_string)]
+  pub fn to_string(&self) -> String {
+    match self.naive_date() {
+      Some(date) => date.to_string(),
+      None => self.days_since_epoch().to_string(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn days_since_epoch_must_be_in_range() {
+    assert_eq!(
+      LocalDate::from_days_since_epoch(-EPOCH_OFFSET)
+        .unwrap()
+        .date,
+      CqlDate(0)
+    );
+    assert_eq!(
+      LocalDate::from_days_since_epoch(i64::from(u32::MAX) - EPOCH_OFFSET)
+        .unwrap()
+        .date,
+      CqlDate(u32::MAX)
+    );
+
+    for days in [
+      -EPOCH_OFFSET - 1,
+      i64::from(u32::MAX) - EPOCH_OFFSET + 1,
+      i64::MAX,
+      i64::MIN,
+    ] {
+      assert!(LocalDate::from_days_since_epoch(days).is_err());
+    }
+  }
+}
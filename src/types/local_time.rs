@@ -0,0 +1,117 @@
+use std::str::FromStr;
+
+use chrono::{NaiveTime, Timelike};
+use napi::Result;
+use scylla::frame::value::CqlTime;
+
+const NANOSECONDS_PER_DAY: i64 = 86_400_000_000_000;
+
+/// Native CQL `time` representation, a time of day with nanosecond precision and without a time zone.
+#[napi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalTime {
+  pub(crate) time: CqlTime,
+}
+
+impl From<CqlTime> for LocalTime {
+  fn from(time: CqlTime) -> Self {
+    Self { time }
+  }
+}
+
+impl From<&LocalTime> for CqlTime {
+  fn from(value: &LocalTime) -> Self {
+    value.time
+  }
+}
+
+impl LocalTime {
+  fn naive_time(&self) -> NaiveTime {
+    // Always in range, it is checked when the time is created
+    self.time.try_into().unwrap_or(NaiveTime::MIN)
+  }
+}
+
+#[napi]
+impl LocalTime {
+  /// Creates a time from its hour (0-23), minute, second and nanosecond.
+  #[napi(constructor)]
+  pub fn new(hour: u32, minute: u32, second: u32, nanosecond: Option<u32>) -> Result<Self> {
+    let nanosecond = nanosecond.unwrap_or(0);
+
+    NaiveTime::from_hms_nano_opt(hour, minute, second, nanosecond)
+      .filter(|time| time.nanosecond() < 1_000_000_000)
+      .and_then(|time| CqlTime::try_from(time).ok())
+      .map(|time| Self { time })
+      .ok_or_else(|| {
+        napi::Error::new(
+          napi::Status::InvalidArg,
+          format!("Invalid time: {hour}:{minute}:{second}.{nanosecond}"),
+        )
+      })
+  }
+
+  /// Parses a time in the `hh:mm:ss[.fffffffff]` format.
+  #[napi]
+  pub fn from_string(str: String) -> Result<LocalTime> {
+    NaiveTime::from_str(&str)
+      .map_err(|e| e.to_string())
+      .and_then(|time| CqlTime::try_from(time).map_err(|e| e.to_string()))
+      .map(|time| Self { time })
+      .map_err(|e| {
+        napi::Error::new(
+          napi::Status::InvalidArg,
+          format!("Failed to parse time: {}", e),
+        )
+      })
+  }
+
+  /// Creates a time from the number of nanoseconds since midnight.
+  #[napi]
+  pub fn from_nanosecond_of_day(nanoseconds: i64) -> Result<LocalTime> {
+    if !(0..NANOSECONDS_PER_DAY).contains(&nanoseconds) {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        format!("Time out of range: {nanoseconds} nanoseconds since midnight"),
+      ));
+    }
+
+    Ok(Self {
+      time: CqlTime(nanoseconds),
+    })
+  }
+
+  /// Nanoseconds since midnight.
+  #[napi(getter)]
+  pub fn nanosecond_of_day(&self) -> i64 {
+    self.time.0
+  }
+
+  #[napi(getter)]
+  pub fn hour(&self) -> u32 {
+    self.naive_time().hour()
+  }
+
+  #[napi(getter)]
+  pub fn minute(&self) -> u32 {
+    self.naive_time().minute()
+  }
+
+  #[napi(getter)]
+  pub fn second(&self) -> u32 {
+    self.naive_time().second()
+  }
+
+  /// Nanoseconds within the current second.
+  #[napi(getter)]
+  pub fn nanosecond(&self) -> u32 {
+    self.naive_time().nanosecond()
+  }
+
+  /// Returns the time in the `hh:mm:ss[.fffffffff]` format.
+  #[napi]
+  #[allow(clippy::inherent_to_string)]
+  pub fn to_string(&self) -> String {
+    self.naive_time().to_string()
+  }
+}
@@ -1,10 +1,15 @@
+pub mod blob;
 pub mod decimal;
 pub mod double;
 pub mod duration;
 pub mod float;
 pub mod list;
+pub mod local_date;
+pub mod local_time;
 pub mod map;
 pub mod set;
 pub mod tracing;
+pub mod tuple;
+pub mod unset;
 pub mod uuid;
 pub mod varint;
@@ -3,7 +3,7 @@ use std::{collections::HashMap, net::IpAddr};
 use napi::Either;
 use serde::Serialize;
 
-use crate::helpers::query_results::WithMapType;
+use crate::helpers::query_results::JSQueryRow;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CqlTimestampWrapper(pub scylla::frame::value::CqlTimestamp);
@@ -87,5 +87,4 @@ impl From<scylla::tracing::TracingEvent> for TracingEvent {
   }
 }
 
-pub type TracingReturn =
-  HashMap<String, Either<Vec<HashMap<String, WithMapType>>, serde_json::Value>>;
+pub type TracingReturn = HashMap<String, Either<Vec<JSQueryRow>, serde_json::Value>>;
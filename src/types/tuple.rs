@@ -0,0 +1,40 @@
+use scylla::frame::response::result::CqlValue;
+
+use crate::helpers::{cql_value_bridge::ParameterWithMapType, to_cql_value::ToCqlValue};
+
+/// A tuple of any CqlType, elements can be `null`
+#[napi]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tuple {
+  pub(crate) inner: Vec<Option<CqlValue>>,
+}
+
+impl From<Vec<Option<CqlValue>>> for Tuple {
+  fn from(inner: Vec<Option<CqlValue>>) -> Self {
+    Self { inner }
+  }
+}
+
+impl From<&Tuple> for Vec<Option<CqlValue>> {
+  fn from(tuple: &Tuple) -> Self {
+    tuple.inner.clone()
+  }
+}
+
+#[napi]
+impl Tuple {
+  #[napi(constructor, ts_args_type = "values: Array<T | null>")]
+  pub fn new_tuple(values: Vec<Option<ParameterWithMapType>>) -> Tuple {
+    let inner = values
+      .into_iter()
+      .map(|v| v.map(|v| v.to_cql_value()))
+      .collect();
+    Tuple { inner }
+  }
+
+  #[napi]
+  #[allow(clippy::inherent_to_string)]
+  pub fn to_string(&self) -> String {
+    format!("{:?}", self.inner)
+  }
+}
@@ -0,0 +1,28 @@
+/// Marks a bound value as unset: the column is left untouched instead of being overwritten with `null`, which
+/// avoids creating tombstones when only some columns of a prepared statement are updated.
+///
+/// Use the exported `UNSET` constant rather than creating new instances.
+///
+/// # Example
+/// ```javascript
+/// const update = await session.prepare("UPDATE users SET name = ?, email = ? WHERE id = ?");
+///
+/// await session.execute(update, ["Alice", UNSET, id]);
+/// ```
+#[napi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Unset {}
+
+#[napi]
+impl Unset {
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    Self {}
+  }
+
+  #[napi]
+  #[allow(clippy::inherent_to_string)]
+  pub fn to_string(&self) -> String {
+    "UNSET".to_string()
+  }
+}